@@ -0,0 +1,245 @@
+use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+
+use rand::Rng;
+
+use crate::ligue1::{atome::*, decision::*, molecule::*, state::*};
+
+pub const NB_TOURS_MAX: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurJoueur {
+    Delai,
+    Termine,
+    Io(String),
+}
+
+/// Un bot vu par l'arbitre: il reçoit l'entrée brute d'un tour et renvoie ses lignes de commande.
+pub trait Joueur {
+    fn jouer(
+        &mut self,
+        entree: &str,
+        nb_actions: usize,
+        tour: u32,
+    ) -> Result<Vec<String>, ErreurJoueur>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resultat {
+    pub gagnant: Option<Owner>,
+    pub nb_organes: OwnerMap<usize>,
+    pub ressources: OwnerMap<Ressource>,
+    pub nb_tours: u32,
+    pub forfait: Option<(Owner, ErreurJoueur)>,
+}
+
+impl Display for Resultat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nom = |owner: Owner| match owner {
+            Owner::Me => "joueur 1",
+            Owner::Ennemy => "joueur 2",
+        };
+        match self.gagnant {
+            Some(owner) => writeln!(f, "gagnant: {}", nom(owner))?,
+            None => writeln!(f, "égalité")?,
+        };
+        if let Some((owner, erreur)) = &self.forfait {
+            writeln!(f, "forfait de {}: {:?}", nom(*owner), erreur)?;
+        }
+        writeln!(f, "tours: {}", self.nb_tours)?;
+        for owner in [Owner::Me, Owner::Ennemy] {
+            writeln!(
+                f,
+                "{}: {} organes, {:?}",
+                nom(owner),
+                self.nb_organes.get(owner),
+                self.ressources.get(owner)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Fait jouer deux bots jusqu'à la fin de la partie; le joueur 1 joue `Owner::Me`.
+pub fn arbitrer(
+    mut state: InitState,
+    joueur_1: &mut dyn Joueur,
+    joueur_2: &mut dyn Joueur,
+) -> Resultat {
+    let dimension = state.dimension();
+    let mut tour: u32 = 0;
+    let mut forfait: Option<(Owner, ErreurJoueur)> = None;
+    while tour < NB_TOURS_MAX
+        && state.nb_organe(Owner::Me) > 0
+        && state.nb_organe(Owner::Ennemy) > 0
+    {
+        let mut decisions: OwnerMap<Vec<Decision>> = OwnerMap::default();
+        let joueurs: [(Owner, &mut dyn Joueur); 2] =
+            [(Owner::Me, &mut *joueur_1), (Owner::Ennemy, &mut *joueur_2)];
+        for (owner, joueur) in joueurs {
+            let mut entree = match tour {
+                0 => entree_initiale(dimension),
+                _ => String::new(),
+            };
+            entree.push_str(&entree_tour(&state, owner));
+            match joueur.jouer(&entree, state.roots().get(owner).len(), tour) {
+                Ok(lignes) => {
+                    decisions = decisions.update(owner, |_| {
+                        lignes
+                            .iter()
+                            .filter_map(|ligne| Decision::from_command(ligne, owner))
+                            .collect()
+                    })
+                }
+                Err(erreur) => {
+                    forfait.get_or_insert((owner, erreur));
+                }
+            }
+        }
+        if forfait.is_some() {
+            break;
+        }
+        state = simuler_tour(&state, decisions);
+        tour += 1;
+    }
+
+    let nb_organes = OwnerMap::new(state.nb_organe(Owner::Me), state.nb_organe(Owner::Ennemy));
+    let gagnant = match &forfait {
+        Some((perdant, _)) => Some(perdant.switch_side()),
+        // À nombre d'organes égal, le plus gros stock de protéines l'emporte.
+        None => match nb_organes
+            .get(Owner::Me)
+            .cmp(nb_organes.get(Owner::Ennemy))
+            .then_with(|| {
+                let total = |owner| state.ressource().get(owner).total();
+                total(Owner::Me).cmp(&total(Owner::Ennemy))
+            }) {
+            Ordering::Greater => Some(Owner::Me),
+            Ordering::Less => Some(Owner::Ennemy),
+            Ordering::Equal => None,
+        },
+    };
+    Resultat {
+        gagnant,
+        nb_organes,
+        ressources: state.ressource(),
+        nb_tours: tour,
+        forfait,
+    }
+}
+
+pub fn entree_initiale(dimension: Dimension) -> String {
+    format!("{} {}\n", dimension.width, dimension.height)
+}
+
+/// Entrée d'un tour telle que l'attend `parsing::parser_tour`, du point de vue de `joueur`.
+pub fn entree_tour(state: &dyn State, joueur: Owner) -> String {
+    let lignes: Vec<String> = state
+        .dimension()
+        .coords()
+        .filter_map(|coord| state.get_by_coord(coord))
+        .filter_map(|cell| ligne_entite(cell, joueur))
+        .collect();
+    let mut entree = format!("{}\n", lignes.len());
+    for ligne in lignes {
+        entree.push_str(&ligne);
+        entree.push('\n');
+    }
+    for owner in [joueur, joueur.switch_side()] {
+        let ressource = *state.ressource().get(owner);
+        let stock: Vec<String> = Protein::all()
+            .iter()
+            .map(|p| ressource.get(p).to_string())
+            .collect();
+        entree.push_str(&stock.join(" "));
+        entree.push('\n');
+    }
+    entree.push_str(&format!("{}\n", state.roots().get(joueur).len()));
+    entree
+}
+
+fn ligne_entite(cell: Cell, joueur: Owner) -> Option<String> {
+    let Coord { x, y } = cell.coord;
+    match cell.entity {
+        Entity::Void => None,
+        Entity::Wall => Some(format!("{x} {y} WALL -1 0 X 0 0")),
+        Entity::Protein(prot) => Some(format!("{x} {y} {} -1 0 X 0 0", prot.to_command())),
+        Entity::Organe(org) => Some(format!(
+            "{x} {y} {} {} {} {} {} {}",
+            org.organe_type.to_command(),
+            if org.owner == joueur { 1 } else { 0 },
+            org.id.to_command(),
+            org.dir.to_command(),
+            org.parent_id.to_command(),
+            org.root_id.to_command()
+        )),
+    }
+}
+
+/// Carte symétrique par rapport au centre, une racine par joueur.
+pub fn generer_carte<R: Rng>(rng: &mut R) -> InitState {
+    let height: u8 = rng.gen_range(8..=12);
+    let width: u8 = height * 2;
+    let dimension = Dimension { height, width };
+    let symetrique = |coord: Coord| Coord {
+        x: width - 1 - coord.x,
+        y: height - 1 - coord.y,
+    };
+    let racine = Coord {
+        x: rng.gen_range(1..width / 4),
+        y: rng.gen_range(1..height - 1),
+    };
+
+    let mut cells: CoordMap<Cell> = HashMap::new();
+    for coord in dimension.coords().filter(|c| c.x < width / 2) {
+        if coord.x.abs_diff(racine.x) + coord.y.abs_diff(racine.y) <= 1 {
+            continue;
+        }
+        let tirage: f64 = rng.gen();
+        let entity = if tirage < 0.12 {
+            Entity::Wall
+        } else if tirage < 0.2 {
+            Entity::Protein(Protein::all()[rng.gen_range(0..4)])
+        } else {
+            continue;
+        };
+        cells.insert(coord, Cell { coord, entity });
+        cells.insert(
+            symetrique(coord),
+            Cell {
+                coord: symetrique(coord),
+                entity,
+            },
+        );
+    }
+    for (num, owner, coord) in [
+        (1, Owner::Me, racine),
+        (2, Owner::Ennemy, symetrique(racine)),
+    ] {
+        let id = Id::new(owner, num);
+        cells.insert(
+            coord,
+            Cell {
+                coord,
+                entity: Entity::Organe(Organe {
+                    id,
+                    parent_id: Id::zero(owner),
+                    root_id: id,
+                    organe_type: OrganeType::Root,
+                    dir: Direction::N,
+                    owner,
+                }),
+            },
+        );
+    }
+
+    InitState::new(
+        dimension,
+        Ressource::new(10, 10, 10, 10),
+        Ressource::new(10, 10, 10, 10),
+        ActionCount::new(1),
+        dimension
+            .coords()
+            .filter_map(|coord| cells.remove(&coord))
+            .collect(),
+    )
+}
@@ -0,0 +1,3 @@
+fn main() {
+    codingame::ligue1::main();
+}
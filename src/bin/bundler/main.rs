@@ -0,0 +1,311 @@
+//! Aplatit le bot en un seul fichier soumissible sur CodinGame.
+//!
+//! Les `mod x;` sont remplacés par le contenu des fichiers en gardant l'arborescence des
+//! modules, si bien que les chemins `crate::` et `super::` restent valides tels quels.
+//! Les crates externes sont remplacées par les modules de `vendor/`, puis commentaires et
+//! blancs superflus sont retirés pour tenir sous la limite de taille de CodinGame.
+
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+const USAGE: &str = "usage: bundler [--src DOSSIER] [--module NOM] [--output FICHIER]";
+
+const CRATES_VENDUES: [(&str, &str); 2] = [
+    ("rand", include_str!("vendor/rand.rs")),
+    ("itertools", include_str!("vendor/itertools.rs")),
+];
+
+fn sans_visibilite(debut: &str) -> &str {
+    ["pub(crate) ", "pub(super) ", "pub "]
+        .iter()
+        .find_map(|vis| debut.strip_prefix(vis))
+        .unwrap_or(debut)
+}
+
+fn declaration_mod(ligne: &str) -> Option<(&str, &str)> {
+    let debut = ligne.trim_start();
+    let indentation = &ligne[..ligne.len() - debut.len()];
+    let sans_visibilite = sans_visibilite(debut);
+    let nom = sans_visibilite
+        .strip_prefix("mod ")?
+        .trim_end()
+        .strip_suffix(';')?
+        .trim();
+    let est_identifiant = !nom.is_empty() && nom.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if !est_identifiant {
+        return None;
+    }
+    let entete = &debut[..debut.len() - sans_visibilite.len()];
+    Some((&ligne[..indentation.len() + entete.len()], nom))
+}
+
+fn lire_module(dossier: &Path, nom: &str) -> io::Result<(String, PathBuf)> {
+    let fichier = dossier.join(format!("{nom}.rs"));
+    match fs::read_to_string(&fichier) {
+        Ok(contenu) => Ok((contenu, dossier.join(nom))),
+        Err(_) => {
+            let dossier_module = dossier.join(nom);
+            let contenu = fs::read_to_string(dossier_module.join("mod.rs"))?;
+            Ok((contenu, dossier_module))
+        }
+    }
+}
+
+fn inliner(source: &str, dossier: &Path) -> io::Result<String> {
+    let mut resultat = String::new();
+    for ligne in source.lines() {
+        match declaration_mod(ligne) {
+            Some((entete, nom)) => {
+                let (contenu, dossier_enfant) = lire_module(dossier, nom)?;
+                let contenu = inliner(&contenu, &dossier_enfant)?;
+                resultat.push_str(&format!("{entete}mod {nom} {{\n{contenu}}}\n"));
+            }
+            None => {
+                resultat.push_str(ligne);
+                resultat.push('\n');
+            }
+        }
+    }
+    Ok(resultat)
+}
+
+fn precede_par_chemin(source: &str, position: usize) -> bool {
+    source[..position]
+        .chars()
+        .next_back()
+        .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == ':')
+}
+
+fn reecrire_ligne(ligne: &str, motif: &str) -> String {
+    let mut resultat = String::with_capacity(ligne.len());
+    let mut reste = 0;
+    for (position, _) in ligne.match_indices(motif) {
+        if precede_par_chemin(ligne, position) {
+            continue;
+        }
+        resultat.push_str(&ligne[reste..position]);
+        resultat.push_str("crate::");
+        reste = position;
+    }
+    resultat.push_str(&ligne[reste..]);
+    resultat
+}
+
+/// Remplace `nom::` par `crate::nom::` dans les `use`, là où `nom` commence un chemin; les
+/// chaînes et les commentaires sont laissés tels quels.
+fn reecrire_chemins(source: &str, nom: &str) -> String {
+    let motif = format!("{nom}::");
+    let mut resultat = String::with_capacity(source.len());
+    let mut dans_use = false;
+    for ligne in source.lines() {
+        dans_use = dans_use || sans_visibilite(ligne.trim_start()).starts_with("use ");
+        match dans_use {
+            true => resultat.push_str(&reecrire_ligne(ligne, &motif)),
+            false => resultat.push_str(ligne),
+        }
+        resultat.push('\n');
+        dans_use = dans_use && !ligne.contains(';');
+    }
+    resultat
+}
+
+fn dependances(source: &str, candidats: &[(String, String)]) -> Vec<String> {
+    candidats
+        .iter()
+        .map(|(_, nom)| nom)
+        .filter(|nom| source.contains(&format!("crate::{nom}")))
+        .cloned()
+        .collect()
+}
+
+// Paires de caractères qui formeraient un autre jeton si l'on retirait l'espace entre eux.
+const JETONS_COLLES: [&str; 24] = [
+    "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=", "*=", "/=", "%=", "^=", "&=",
+    "|=", "<<", ">>", "..", "//", "/*", "*/", "<-",
+];
+
+fn est_mot(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '\'' | '"')
+}
+
+fn blanc_necessaire(avant: char, apres: char) -> bool {
+    let paire: String = [avant, apres].iter().collect();
+    (est_mot(avant) && est_mot(apres)) || JETONS_COLLES.contains(&paire.as_str())
+}
+
+/// Longueur du littéral qui commence à `debut`: chaîne, chaîne brute ou caractère. Un `'`
+/// qui ouvre une durée de vie n'est pas un littéral.
+fn litteral(source: &[char], debut: usize) -> Option<usize> {
+    let precede_par_mot =
+        debut > 0 && (source[debut - 1].is_alphanumeric() || source[debut - 1] == '_');
+    match source[debut] {
+        'r' if !precede_par_mot => {
+            let dieses = source[debut + 1..]
+                .iter()
+                .take_while(|c| **c == '#')
+                .count();
+            if source.get(debut + 1 + dieses) != Some(&'"') {
+                return None;
+            }
+            let mut fin = vec!['"'];
+            fin.resize(dieses + 1, '#');
+            let corps = debut + 2 + dieses;
+            let position = (corps..source.len()).find(|i| source[*i..].starts_with(&fin))?;
+            Some(position + fin.len() - debut)
+        }
+        '"' => {
+            let mut i = debut + 1;
+            while source[i] != '"' {
+                i += if source[i] == '\\' { 2 } else { 1 };
+            }
+            Some(i + 1 - debut)
+        }
+        '\'' => match (source.get(debut + 1), source.get(debut + 2)) {
+            (Some('\\'), _) => {
+                let fin = (debut + 2..source.len()).find(|i| source[*i] == '\'')?;
+                Some(fin + 1 - debut)
+            }
+            (Some(_), Some('\'')) => Some(3),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Retire les commentaires et les blancs superflus: le fichier doit tenir sous la limite de
+/// 100 000 caractères de CodinGame. Un blanc qui contenait un saut de ligne en reste un.
+fn compacter(source: &str) -> String {
+    let source: Vec<char> = source.chars().collect();
+    let mut resultat = String::with_capacity(source.len());
+    let mut blanc: Option<char> = None;
+    let mut i = 0;
+    while i < source.len() {
+        let reste = &source[i..];
+        if reste[0].is_whitespace() {
+            blanc = Some(if reste[0] == '\n' {
+                '\n'
+            } else {
+                blanc.unwrap_or(' ')
+            });
+            i += 1;
+            continue;
+        }
+        if reste.starts_with(&['/', '/']) {
+            i += reste.iter().take_while(|c| **c != '\n').count();
+            blanc.get_or_insert(' ');
+            continue;
+        }
+        if reste.starts_with(&['/', '*']) {
+            let mut profondeur = 0;
+            loop {
+                match &source[i..] {
+                    ['/', '*', ..] => (profondeur, i) = (profondeur + 1, i + 2),
+                    ['*', '/', ..] => (profondeur, i) = (profondeur - 1, i + 2),
+                    _ => i += 1,
+                }
+                if profondeur == 0 {
+                    break;
+                }
+            }
+            blanc.get_or_insert(' ');
+            continue;
+        }
+        let longueur = litteral(&source, i).unwrap_or(1);
+        if let (Some(separateur), Some(avant)) = (blanc.take(), resultat.chars().next_back()) {
+            if blanc_necessaire(avant, source[i]) {
+                resultat.push(separateur);
+            }
+        }
+        resultat.extend(&source[i..i + longueur]);
+        i += longueur;
+    }
+    resultat.push('\n');
+    resultat
+}
+
+fn bundler(src: &Path, module: &str) -> io::Result<String> {
+    let lib = fs::read_to_string(src.join("lib.rs"))?;
+    let modules_lib: Vec<(String, String)> = lib
+        .lines()
+        .filter_map(declaration_mod)
+        .map(|(entete, nom)| (entete.trim_start().to_string(), nom.to_string()))
+        .collect();
+
+    let mut inclus: HashSet<String> = HashSet::new();
+    let mut a_traiter = vec![module.to_string()];
+    let mut contenus: Vec<(String, String)> = Vec::new();
+    while let Some(nom) = a_traiter.pop() {
+        if !inclus.insert(nom.clone()) {
+            continue;
+        }
+        let (contenu, dossier) = lire_module(src, &nom)?;
+        let mut contenu = inliner(&contenu, &dossier)?;
+        for (nom_crate, _) in CRATES_VENDUES {
+            contenu = reecrire_chemins(&contenu, nom_crate);
+        }
+        a_traiter.extend(dependances(&contenu, &modules_lib));
+        contenus.push((nom, contenu));
+    }
+
+    let mut resultat = String::from("#![allow(dead_code, unused_imports, unused_macros)]\n\n");
+    for (entete, nom) in modules_lib.iter() {
+        if let Some((_, contenu)) = contenus.iter().find(|(n, _)| n == nom) {
+            resultat.push_str(&format!("{entete}mod {nom} {{\n{contenu}}}\n\n"));
+        }
+    }
+    for (nom_crate, source) in CRATES_VENDUES {
+        let utilise = contenus
+            .iter()
+            .any(|(_, contenu)| contenu.contains(&format!("crate::{nom_crate}::")));
+        if utilise {
+            resultat.push_str(&format!("mod {nom_crate} {{\n{source}}}\n\n"));
+        }
+    }
+    resultat.push_str(&format!("fn main() {{\n    {module}::main();\n}}\n"));
+    Ok(format!(
+        "// Généré par bundler à partir de {}, ne pas modifier à la main.\n{}",
+        src.display(),
+        compacter(&resultat)
+    ))
+}
+
+fn main() {
+    let mut src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut module = String::from("ligue1");
+    let mut sortie: Option<PathBuf> = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let Some(valeur) = args.next() else {
+            eprintln!("{USAGE}");
+            process::exit(2)
+        };
+        match arg.as_str() {
+            "--src" => src = PathBuf::from(valeur),
+            "--module" => module = valeur,
+            "--output" => sortie = Some(PathBuf::from(valeur)),
+            _ => {
+                eprintln!("{USAGE}");
+                process::exit(2)
+            }
+        }
+    }
+
+    let resultat = bundler(&src, &module).unwrap_or_else(|e| {
+        eprintln!(
+            "impossible d'aplatir {module} depuis {}: {e}",
+            src.display()
+        );
+        process::exit(1)
+    });
+    match sortie {
+        Some(fichier) => fs::write(&fichier, resultat).unwrap_or_else(|e| {
+            eprintln!("impossible d'écrire {}: {e}", fichier.display());
+            process::exit(1)
+        }),
+        None => print!("{resultat}"),
+    }
+}
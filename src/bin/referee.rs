@@ -0,0 +1,130 @@
+use std::{
+    env,
+    io::{self, BufRead, BufReader, Write},
+    process::{self, Child, ChildStdin, Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use codingame::arbitre::{arbitrer, generer_carte, ErreurJoueur, Joueur};
+use rand::{rngs::StdRng, SeedableRng};
+
+const USAGE: &str = "usage: referee [--seed N] [--timeout-ms N] [--verbose] <bot 1> <bot 2>";
+
+struct Processus {
+    enfant: Child,
+    entree: ChildStdin,
+    sortie: Receiver<String>,
+    delai_premier_tour: Duration,
+    delai: Duration,
+}
+
+impl Processus {
+    fn lancer(commande: &str, delai: Duration, verbeux: bool) -> io::Result<Self> {
+        let mut enfant = Command::new("sh")
+            .arg("-c")
+            .arg(commande)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(if verbeux {
+                Stdio::inherit()
+            } else {
+                Stdio::null()
+            })
+            .spawn()?;
+        let entree = enfant.stdin.take().expect("stdin non capturé");
+        let stdout = enfant.stdout.take().expect("stdout non capturé");
+        let (envoi, sortie) = mpsc::channel();
+        thread::spawn(move || {
+            for ligne in BufReader::new(stdout).lines() {
+                let Ok(ligne) = ligne else { break };
+                if envoi.send(ligne).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Self {
+            enfant,
+            entree,
+            sortie,
+            delai_premier_tour: delai.max(Duration::from_millis(1000)),
+            delai,
+        })
+    }
+}
+
+impl Joueur for Processus {
+    fn jouer(
+        &mut self,
+        entree: &str,
+        nb_actions: usize,
+        tour: u32,
+    ) -> Result<Vec<String>, ErreurJoueur> {
+        self.entree
+            .write_all(entree.as_bytes())
+            .and_then(|_| self.entree.flush())
+            .map_err(|e| ErreurJoueur::Io(e.to_string()))?;
+        let delai = match tour {
+            0 => self.delai_premier_tour,
+            _ => self.delai,
+        };
+        // Le délai vaut pour tout le tour, pas pour chaque ligne.
+        let echeance = Instant::now() + delai;
+        (0..nb_actions)
+            .map(|_| {
+                let reste = echeance.saturating_duration_since(Instant::now());
+                self.sortie.recv_timeout(reste).map_err(|e| match e {
+                    RecvTimeoutError::Timeout => ErreurJoueur::Delai,
+                    RecvTimeoutError::Disconnected => ErreurJoueur::Termine,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Drop for Processus {
+    fn drop(&mut self) {
+        let _ = self.enfant.kill();
+        let _ = self.enfant.wait();
+    }
+}
+
+fn main() {
+    let mut seed: u64 = 0;
+    let mut delai = Duration::from_millis(100);
+    let mut verbeux = false;
+    let mut bots: Vec<String> = Vec::new();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut valeur = |nom: &str| -> u64 {
+            args.next().and_then(|v| v.parse().ok()).unwrap_or_else(|| {
+                eprintln!("{nom} attend un nombre\n{USAGE}");
+                process::exit(2)
+            })
+        };
+        match arg.as_str() {
+            "--seed" => seed = valeur("--seed"),
+            "--timeout-ms" => delai = Duration::from_millis(valeur("--timeout-ms")),
+            "--verbose" => verbeux = true,
+            _ => bots.push(arg),
+        }
+    }
+    let [bot_1, bot_2] = &bots[..] else {
+        eprintln!("{USAGE}");
+        process::exit(2)
+    };
+
+    let lancer = |commande: &str| {
+        Processus::lancer(commande, delai, verbeux).unwrap_or_else(|e| {
+            eprintln!("impossible de lancer {commande}: {e}");
+            process::exit(1)
+        })
+    };
+    let mut joueur_1 = lancer(bot_1);
+    let mut joueur_2 = lancer(bot_2);
+
+    let carte = generer_carte(&mut StdRng::seed_from_u64(seed));
+    let resultat = arbitrer(carte, &mut joueur_1, &mut joueur_2);
+    print!("{resultat}");
+}
@@ -1,5 +1,9 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::{self, Display, Formatter},
+};
 
-use crate::ligue1::{atome::*, state::*};
+use crate::ligue1::{atome::*, rendu::*, state::*};
 
 #[derive(Debug, Clone)]
 pub struct StateBuilder {
@@ -42,6 +46,11 @@ impl StateBuilder {
         self.ressources_ami = ressource;
         self
     }
+
+    pub fn with_ressources_ennemy(mut self, ressource: Ressource) -> Self {
+        self.ressources_ennemy = ressource;
+        self
+    }
 }
 
 impl StateBuilder {
@@ -163,7 +172,7 @@ impl OrganismBuilder {
         let mut curr_id = Id::zero(owner);
         let root_id = Id::zero(owner);
         let current_cell = Cell {
-            coord: curr_coord.clone(),
+            coord: curr_coord,
             entity: Entity::Organe(Organe {
                 id: curr_id,
                 parent_id: curr_id,
@@ -175,7 +184,7 @@ impl OrganismBuilder {
         };
         let mut resultat = vec![current_cell];
         for dir in self.decalages.iter() {
-            curr_coord = curr_coord.decaler(dir.clone()).expect("mauvaise direction");
+            curr_coord = curr_coord.decaler(*dir).expect("mauvaise direction");
             let parent_id = curr_id;
             curr_id = curr_id.increment();
             resultat.push(Cell {
@@ -193,3 +202,275 @@ impl OrganismBuilder {
         resultat
     }
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErreurCarte {
+    Entete { ligne: usize, brut: String },
+    Glyphe { ligne: usize, glyphe: String },
+    LargeurIrreguliere { ligne: usize },
+    Orphelin { coord: Coord },
+}
+
+impl Display for ErreurCarte {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ErreurCarte::Entete { ligne, brut } => {
+                write!(
+                    f,
+                    "ligne {ligne}: `ami:`, `ennemi:` ou `actions:` attendu, lu {brut:?}"
+                )
+            }
+            ErreurCarte::Glyphe { ligne, glyphe } => {
+                write!(f, "ligne {ligne}: case illisible {glyphe:?}")
+            }
+            ErreurCarte::LargeurIrreguliere { ligne } => {
+                write!(
+                    f,
+                    "ligne {ligne}: pas le même nombre de cases que la première ligne"
+                )
+            }
+            ErreurCarte::Orphelin {
+                coord: Coord { x, y },
+            } => {
+                write!(f, "organe en ({x}, {y}) relié à aucune racine")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Case {
+    Vide,
+    Mur,
+    Proteine(Protein),
+    Organe(OrganeType, Owner, Direction),
+}
+
+fn lire_case(glyphe: &str) -> Option<Case> {
+    let glyphe = glyphe.trim_end_matches([RECOLTE, CIBLE]).trim_end();
+    let mut chars = glyphe.chars();
+    let premier = match chars.next() {
+        None => return Some(Case::Vide),
+        Some('.') => return chars.next().is_none().then_some(Case::Vide),
+        Some(premier) => premier,
+    };
+    let direction = match chars.next() {
+        None => None,
+        Some(c) => Some(FLECHES.iter().find(|(g, _)| *g == c)?.1),
+    };
+    if chars.next().is_some() {
+        return None;
+    }
+    match (premier, direction) {
+        ('#', None) => return Some(Case::Mur),
+        (p, None) if p.is_ascii_uppercase() => {
+            if let Some(prot) = Protein::from_command(&p.to_string()) {
+                return Some(Case::Proteine(prot));
+            }
+        }
+        _ => {}
+    };
+    let owner = match premier.is_ascii_lowercase() {
+        true => Owner::Me,
+        false => Owner::Ennemy,
+    };
+    let (_, organe_type) = LETTRES_ORGANE
+        .iter()
+        .find(|(g, _)| *g == premier.to_ascii_lowercase())?;
+    Some(Case::Organe(
+        *organe_type,
+        owner,
+        direction.unwrap_or(Direction::N),
+    ))
+}
+
+fn lire_ressource(valeur: &str) -> Option<Ressource> {
+    let nombres: Vec<u32> = valeur
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match nombres[..] {
+        [a, b, c, d] => Some(Ressource::new(a, b, c, d)),
+        _ => None,
+    }
+}
+
+impl StateBuilder {
+    /**
+     * Construit un état depuis une carte ASCII, par exemple:
+     *
+     * ami: 1 1 1 1
+     * ennemi: 1 1 1 1
+     * |r^|b>|h>|A |
+     * |# |. |. |R |
+     *
+     * `.` vide, `#` mur, `A` à `D` protéines, `r` racine, `b` basic, `h` harvester,
+     * `t` tentacle, `s` sporer, en minuscule pour nous et en majuscule pour l'ennemi,
+     * suivis de la direction `^ > v <` (N par défaut; un basic ennemi s'écrit donc `B^`).
+     * Les lignes de tirets et les marques `*` et `!` du rendu sont ignorées. Les id et les
+     * parents sont déduits par un parcours en largeur depuis chaque racine, dans l'ordre de
+     * lecture.
+     */
+    pub fn from_ascii(carte: &str) -> Result<Self, ErreurCarte> {
+        let mut builder = Self::new_carre_vide_3();
+        let mut action_count: Option<ActionCount> = None;
+        let mut lignes: Vec<Vec<Case>> = Vec::new();
+        for (indice, ligne) in carte.lines().enumerate() {
+            let numero = indice + 1;
+            let ligne = ligne.trim();
+            if ligne.chars().all(|c| c == '-') {
+                continue;
+            }
+            let erreur_entete = || ErreurCarte::Entete {
+                ligne: numero,
+                brut: ligne.to_string(),
+            };
+            if let Some(contenu) = ligne.strip_prefix('|') {
+                let cases = contenu
+                    .strip_suffix('|')
+                    .unwrap_or(contenu)
+                    .split('|')
+                    .map(|g| {
+                        lire_case(g.trim()).ok_or_else(|| ErreurCarte::Glyphe {
+                            ligne: numero,
+                            glyphe: g.to_string(),
+                        })
+                    })
+                    .collect::<Result<Vec<Case>, _>>()?;
+                if lignes.first().is_some_and(|l| l.len() != cases.len()) {
+                    return Err(ErreurCarte::LargeurIrreguliere { ligne: numero });
+                }
+                lignes.push(cases);
+                continue;
+            }
+            let (cle, valeur) = ligne.split_once(':').ok_or_else(erreur_entete)?;
+            match cle.trim() {
+                "ami" => {
+                    builder.ressources_ami = lire_ressource(valeur).ok_or_else(erreur_entete)?
+                }
+                "ennemi" => {
+                    builder.ressources_ennemy = lire_ressource(valeur).ok_or_else(erreur_entete)?
+                }
+                "actions" => {
+                    let count = valeur.trim().parse().map_err(|_| erreur_entete())?;
+                    action_count = Some(ActionCount::new(count));
+                }
+                _ => return Err(erreur_entete()),
+            }
+        }
+
+        let taille = |n: usize| u8::try_from(n).unwrap_or(u8::MAX);
+        builder.dimension = Dimension {
+            height: taille(lignes.len()),
+            width: taille(lignes.first().map_or(0, Vec::len)),
+        };
+        let cases: HashMap<Coord, Case> = builder
+            .dimension
+            .coords()
+            .map(|coord| (coord, lignes[coord.y as usize][coord.x as usize]))
+            .collect();
+        builder.cells = builder
+            .dimension
+            .coords()
+            .filter_map(|coord| {
+                let entity = match cases[&coord] {
+                    Case::Mur => Entity::Wall,
+                    Case::Proteine(prot) => Entity::Protein(prot),
+                    Case::Vide | Case::Organe(..) => return None,
+                };
+                Some(Cell { coord, entity })
+            })
+            .collect();
+        builder
+            .cells
+            .extend(deduire_organismes(&builder.dimension, &cases)?);
+        let nb_racines = builder
+            .cells
+            .iter()
+            .filter(|c| {
+                matches!(c.entity, Entity::Organe(org)
+                    if org.owner == Owner::Me && org.organe_type == OrganeType::Root)
+            })
+            .count();
+        builder.action_count = action_count
+            .unwrap_or_else(|| ActionCount::new(u32::try_from(nb_racines).unwrap_or(u32::MAX)));
+        Ok(builder)
+    }
+}
+
+fn deduire_organismes(
+    dimension: &Dimension,
+    cases: &HashMap<Coord, Case>,
+) -> Result<Vec<Cell>, ErreurCarte> {
+    let mut organes: Vec<Cell> = Vec::new();
+    let mut vus: HashSet<Coord> = HashSet::new();
+    for owner in [Owner::Me, Owner::Ennemy] {
+        let mut prochain_id = Id::zero(owner);
+        let racines = dimension.coords().filter(
+            |coord| matches!(cases[coord], Case::Organe(OrganeType::Root, o, _) if o == owner),
+        );
+        for racine in racines.collect::<Vec<_>>() {
+            let root_id = prochain_id;
+            let mut file: VecDeque<(Coord, Id)> = VecDeque::from([(racine, Id::zero(owner))]);
+            while let Some((coord, parent_id)) = file.pop_front() {
+                let Case::Organe(organe_type, _, dir) = cases[&coord] else {
+                    continue;
+                };
+                if !vus.insert(coord) {
+                    continue;
+                }
+                let id = prochain_id;
+                prochain_id = prochain_id.increment();
+                organes.push(Cell {
+                    coord,
+                    entity: Entity::Organe(Organe {
+                        id,
+                        parent_id,
+                        root_id,
+                        organe_type,
+                        dir,
+                        owner,
+                    }),
+                });
+                file.extend(
+                    Direction::all()
+                        .into_iter()
+                        .filter_map(|d| coord.decaler(d))
+                        .filter(|voisin| !vus.contains(voisin))
+                        .filter(|voisin| {
+                            matches!(cases.get(voisin), Some(Case::Organe(t, o, _))
+                                if *o == owner && *t != OrganeType::Root)
+                        })
+                        .map(|voisin| (voisin, id)),
+                );
+            }
+        }
+    }
+    match dimension
+        .coords()
+        .find(|coord| matches!(cases[coord], Case::Organe(..)) && !vus.contains(coord))
+    {
+        Some(coord) => Err(ErreurCarte::Orphelin { coord }),
+        None => Ok(organes),
+    }
+}
+
+/// Dessine un état dans le format lu par `StateBuilder::from_ascii`.
+pub fn dessiner(state: &dyn State) -> String {
+    let mut texte = String::new();
+    let _ = ecrire_ressources(&mut texte, state.ressource());
+    let dimension = state.dimension();
+    for y in 0..dimension.height {
+        texte.push('|');
+        for x in 0..dimension.width {
+            let entity = state
+                .get_by_coord(Coord { x, y })
+                .map_or(Entity::Void, |c| c.entity);
+            texte.push_str(&glyphe(entity));
+            texte.push('|');
+        }
+        texte.push('\n');
+    }
+    texte
+}
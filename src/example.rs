@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 #![allow(unused)]
+#![allow(clippy::explicit_counter_loop)]
 
 use std::io;
 
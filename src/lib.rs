@@ -1,3 +1,4 @@
+pub mod arbitre;
 pub mod common;
 mod example;
 pub mod ligue1;
@@ -1,18 +1,79 @@
-use ai::{make_decision, Managing};
-use atome::ToCommand;
-use parsing::{parser_dimension, parser_tour};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufWriter},
+};
+
+use ai::{make_decision, Managing, ReponseEnnemie};
+use atome::{ActionCount, Dimension, ToCommand};
+use decision::Decision;
+use evaluation::Poids;
+use parsing::{parser_dimension, parser_tour, Lecteur};
 use rand::prelude::*;
+use replay::Enregistreur;
+
+pub const VARIABLE_REPLAY: &str = "CODINGAME_REPLAY";
+pub const VARIABLE_POIDS: &str = "CODINGAME_POIDS";
+// La plus grande carte de l'arbitre, si la première ligne est illisible.
+const DIMENSION_MAX: Dimension = Dimension {
+    height: 12,
+    width: 24,
+};
 
 pub fn main() {
-    let dimension = parser_dimension();
-    let mut managing = Managing::new().with_rng(rand::rngs::StdRng::seed_from_u64(33));
+    let mut lecteur = Lecteur::new(io::stdin().lock());
+    if let Ok(chemin) = env::var(VARIABLE_REPLAY) {
+        match File::create(&chemin) {
+            Ok(fichier) => {
+                let enregistreur = Enregistreur::new(Box::new(BufWriter::new(fichier)));
+                lecteur = lecteur.with_enregistreur(enregistreur);
+            }
+            Err(erreur) => eprintln!("replay {chemin} impossible: {erreur}"),
+        }
+    }
+    let poids = match env::var(VARIABLE_POIDS) {
+        Ok(chemin) => Poids::charger(&chemin).unwrap_or_else(|erreur| {
+            eprintln!("poids {chemin} impossibles: {erreur}");
+            Poids::default()
+        }),
+        Err(_) => Poids::default(),
+    };
+    let dimension = match parser_dimension(&mut lecteur) {
+        Ok(dimension) => dimension,
+        Err(_) if lecteur.est_fini() => return,
+        Err(erreur) => {
+            eprintln!("{erreur}");
+            DIMENSION_MAX
+        }
+    };
+    let mut managing = Managing::new()
+        .with_rng(StdRng::seed_from_u64(33))
+        .with_reponse_ennemie(ReponseEnnemie::PireCas(3));
+    let mut action_count = ActionCount::default();
     loop {
         managing.restart();
-        let game_state = parser_tour(dimension);
-        eprintln!("Game State: {}", &game_state);
-        let decisions = make_decision(game_state, &mut managing);
+        if let Some(enregistreur) = lecteur.enregistreur() {
+            enregistreur.nouveau_tour();
+        }
+        let decisions = match parser_tour(&mut lecteur, dimension) {
+            Ok(game_state) => {
+                eprintln!("Game State: {}", &game_state);
+                action_count = game_state.get_action_count();
+                make_decision(game_state, &mut managing, &poids)
+            }
+            Err(_) if lecteur.est_fini() => return,
+            // Un tour illisible est joué en WAIT plutôt que de déclarer forfait.
+            Err(erreur) => {
+                eprintln!("{erreur}");
+                action_count.into_iter().map(|_| Decision::Wait).collect()
+            }
+        };
         for decision in decisions {
-            println!("{}", decision.to_command());
+            let commande = decision.to_command();
+            if let Some(enregistreur) = lecteur.enregistreur() {
+                enregistreur.sortie(&commande);
+            }
+            println!("{commande}");
         }
         managing.next_turn();
     }
@@ -21,8 +82,8 @@ pub fn main() {
 pub mod ai {
     use rand::prelude::*;
     use std::{
+        collections::{hash_map::Entry, HashMap},
         ops::{Range, Sub},
-        rc::Rc,
         time::{Duration, Instant},
     };
 
@@ -30,87 +91,530 @@ pub mod ai {
 
     use itertools::iproduct;
 
-    use super::{atome::*, decision::*, molecule::*, state::*};
+    use super::{
+        atome::*,
+        decision::*,
+        distance::Distances,
+        economie::Economie,
+        evaluation::Evaluator,
+        evolution::planifier_evolution,
+        mcts::{planifier_mcts, Rollout},
+        molecule::*,
+        smitsimax::{planifier_smitsimax, Decouplage},
+        state::*,
+    };
 
-    pub fn make_decision(state: InitState, managing: &mut Managing) -> Vec<Decision> {
-        let state_pointer = Rc::new(state);
-        let mut planification_iter = planifier(state_pointer.clone(), managing)
+    pub fn make_decision(
+        state: InitState,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> Vec<Decision> {
+        let mut planification_iter = planifier(&state, managing, evaluateur)
             .take_first_turn()
             .into_iter();
         let mut decisions: Vec<Decision> = Vec::new();
-        for _ in state_pointer.get_action_count() {
+        for _ in state.get_action_count() {
             decisions.push(planification_iter.next().unwrap_or(Decision::Wait));
         }
         decisions
     }
 
-    pub fn planifier(state: Rc<dyn State>, managing: &mut Managing) -> Planification {
-        let mut states: Vec<WeightedState> = vec![WeightedState { state, weight: 1 }];
-        for _i in managing.iterations() {
-            let nouveau_tour = states.into_iter().map(|w| w.state).collect();
-            let intermediaire: Vec<WeightedState> = realiser_tour(nouveau_tour)
-                .into_iter()
-                .map(|s| Rc::new(s) as Rc<dyn State>)
-                .map(|s| juger(s))
+    // Les réponses ennemies coûtent cher: on ne les calcule que pour un sur-échantillon du
+    // faisceau, retenu sur les poids sans réponse.
+    const SURECHANTILLONNAGE: usize = 2;
+
+    /// Planifie selon la stratégie de `managing`, qui garde le plan pour le tour suivant.
+    pub fn planifier(
+        state: &dyn State,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> Planification {
+        let plan = match managing.search_strategy() {
+            SearchStrategy::Mcts(rollout) => planifier_mcts(state, managing, rollout, evaluateur),
+            SearchStrategy::Smitsimax(decouplage) => {
+                planifier_smitsimax(state, managing, decouplage, evaluateur)
+            }
+            SearchStrategy::Evolution => planifier_evolution(state, managing, evaluateur),
+            SearchStrategy::Aleatoire | SearchStrategy::Faisceau => {
+                planifier_par_tours(state, managing, evaluateur)
+            }
+        };
+        managing.garder_plan(plan.clone());
+        plan
+    }
+
+    /// Rejoue le plan gardé sur l'état reçu, jusqu'au premier tour qui n'est plus jouable.
+    pub fn rejouer_plan(state: &dyn State, plan: &Planification) -> Vec<Plateau> {
+        rejouer_tours(state, plan)
+            .into_iter()
+            .map(|(_, joue)| finir(joue))
+            .collect()
+    }
+
+    // Comme `rejouer_plan`, mais garde pour chaque tour le plateau d'avant et celui où nos
+    // coups sont joués, tour non terminé.
+    fn rejouer_tours(state: &dyn State, plan: &Planification) -> Vec<(Plateau, Plateau)> {
+        let mut avant = Plateau::new(state);
+        let mut retour: Vec<(Plateau, Plateau)> = Vec::new();
+        for tour in plan.clone().take_content() {
+            let racines = avant.a_jouer(Owner::Me).to_vec();
+            let Some(coups) = par_racine(&avant, &racines, tour) else {
+                break;
+            };
+            let mut joue = avant.clone();
+            let valide = racines.into_iter().zip(coups).all(|(root_id, coup)| {
+                coups_possibles(&joue, root_id).contains(&coup)
+                    && jouer_coup(&mut joue, root_id, coup)
+            });
+            if !valide {
+                break;
+            }
+            retour.push((avant, joue.clone()));
+            avant = finir(joue);
+        }
+        retour
+    }
+
+    /// Range les coups d'un tour sous la racine de leur parent, retrouvée par id et non par
+    /// position: si l'ennemi a poussé entre-temps, les id prévus ne désignent plus rien.
+    fn par_racine(state: &dyn State, racines: &[Id], tour: Vec<Decision>) -> Option<Vec<Decision>> {
+        if tour.is_empty() || tour.len() != racines.len() {
+            return None;
+        }
+        let mut coups = vec![Decision::Wait; racines.len()];
+        for coup in tour {
+            let parent_id = match coup {
+                Decision::Wait => continue,
+                Decision::Grow(grow) => grow.parent_id,
+                Decision::Spore(spore) => spore.parent_id,
+            };
+            let root_id = state.get_by_id(parent_id)?.root_id;
+            let indice = racines.iter().position(|id| *id == root_id)?;
+            if coups[indice] != Decision::Wait {
+                return None;
+            }
+            coups[indice] = coup;
+        }
+        Some(coups)
+    }
+
+    fn planifier_par_tours(
+        state: &dyn State,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> Planification {
+        let semences = managing
+            .plan_precedent()
+            .map(|plan| rejouer_tours(state, plan))
+            .unwrap_or_default();
+        let mut states: Vec<WeightedState> = vec![WeightedState {
+            state: Plateau::new(state),
+            score: 0.0,
+        }];
+        for i in managing.iterations() {
+            let avants: Vec<Plateau> = states.into_iter().map(|w| w.state).collect();
+            let joues: Vec<(usize, Plateau)> = avants
+                .iter()
+                .enumerate()
+                .flat_map(|(indice, avant)| {
+                    realiser_tour(vec![avant.clone()], Owner::Me, managing.elagage())
+                        .into_iter()
+                        .map(move |plateau| (indice, plateau))
+                })
                 .collect();
             let nb_to_choose = managing.nb_to_choose();
-            states = intermediaire
-                .choose_multiple_weighted(managing.rng(), nb_to_choose, |w| w.weight)
-                .expect("Erreur dans le choose")
-                .cloned()
-                .collect();
+            states = match managing.reponse_ennemie() {
+                ReponseEnnemie::Ignorer => selectionner(
+                    dedupliquer(
+                        joues
+                            .into_iter()
+                            .map(|(_, plateau)| juger(finir(plateau), evaluateur)),
+                        |w| (w.state.zobrist(), w.score),
+                    ),
+                    nb_to_choose,
+                    |w| w.score,
+                    managing,
+                ),
+                _ => {
+                    let intermediaire = dedupliquer(
+                        joues.into_iter().map(|(indice, plateau)| {
+                            let fin = juger(finir(plateau.clone()), evaluateur);
+                            (indice, plateau, fin.score, fin.state.zobrist())
+                        }),
+                        |(_, _, score, empreinte)| (*empreinte, *score),
+                    );
+                    let nb_candidats = nb_to_choose.saturating_mul(SURECHANTILLONNAGE);
+                    let candidats =
+                        selectionner(intermediaire, nb_candidats, |(_, _, w, _)| *w, managing);
+                    let reponses: Vec<WeightedState> = candidats
+                        .into_iter()
+                        .map(|(indice, plateau, _, _)| {
+                            repondre(&avants[indice], plateau, managing, evaluateur)
+                        })
+                        .collect();
+                    selectionner(
+                        dedupliquer(reponses, |w| (w.state.zobrist(), w.score)),
+                        nb_to_choose,
+                        |w| w.score,
+                        managing,
+                    )
+                }
+            };
+            // Ce qui reste valide du plan précédent survit à la sélection.
+            if let Some((avant, joue)) = semences.get(usize::from(i)) {
+                let semence = match managing.reponse_ennemie() {
+                    ReponseEnnemie::Ignorer => juger(finir(joue.clone()), evaluateur),
+                    _ => repondre(avant, joue.clone(), managing, evaluateur),
+                };
+                let empreinte = semence.state.zobrist();
+                if states.iter().all(|w| w.state.zobrist() != empreinte) {
+                    states.push(semence);
+                }
+            }
 
             if managing.is_finished() {
                 break;
             };
         }
 
-        states
-            .choose_weighted(managing.rng(), |w| w.weight)
-            .map(|s| s.state.planification())
-            .unwrap_or_default()
+        match (managing.search_strategy(), managing.reponse_ennemie()) {
+            (SearchStrategy::Aleatoire, ReponseEnnemie::Ignorer) => {
+                let scores: Vec<f64> = states.iter().map(|w| w.score).collect();
+                let poids = managing.ponderation().poids(&scores);
+                let indices: Vec<usize> = (0..states.len()).collect();
+                indices
+                    .choose_weighted(managing.rng(), |i| poids[*i])
+                    .map(|i| states[*i].state.planification())
+                    .unwrap_or_default()
+            }
+            // Les notes tiennent compte des réponses ennemies: on garde le plan qui résiste le mieux.
+            // À note égale, le premier dans l'ordre du faisceau l'emporte, comme dans `selectionner`.
+            _ => states
+                .into_iter()
+                .min_by(|a, b| b.score.total_cmp(&a.score))
+                .map(|s| s.state.planification())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn selectionner<T: Clone>(
+        elements: Vec<T>,
+        nb: usize,
+        score: impl Fn(&T) -> f64,
+        managing: &mut Managing,
+    ) -> Vec<T> {
+        match managing.search_strategy() {
+            SearchStrategy::Aleatoire => {
+                let scores: Vec<f64> = elements.iter().map(&score).collect();
+                let poids = managing.ponderation().poids(&scores);
+                let indices: Vec<usize> = (0..elements.len()).collect();
+                indices
+                    .choose_multiple_weighted(managing.rng(), nb, |i| poids[*i])
+                    .expect("Erreur dans le choose")
+                    .map(|i| elements[*i].clone())
+                    .collect()
+            }
+            SearchStrategy::Faisceau
+            | SearchStrategy::Mcts(_)
+            | SearchStrategy::Smitsimax(_)
+            | SearchStrategy::Evolution => {
+                let mut elements = elements;
+                // Tri stable: à note égale, l'ordre de génération départage.
+                elements.sort_by(|a, b| score(b).total_cmp(&score(a)));
+                elements.truncate(nb);
+                elements
+            }
+        }
+    }
+
+    // Garde un seul représentant, le mieux noté, des plateaux de même empreinte.
+    fn dedupliquer<T>(
+        elements: impl IntoIterator<Item = T>,
+        cle: impl Fn(&T) -> (u64, f64),
+    ) -> Vec<T> {
+        let mut positions: HashMap<u64, usize> = HashMap::new();
+        let mut retour: Vec<T> = Vec::new();
+        for element in elements {
+            let (empreinte, score) = cle(&element);
+            match positions.entry(empreinte) {
+                Entry::Occupied(position) => {
+                    let garde = &mut retour[*position.get()];
+                    if cle(garde).1 < score {
+                        *garde = element;
+                    }
+                }
+                Entry::Vacant(position) => {
+                    position.insert(retour.len());
+                    retour.push(element);
+                }
+            }
+        }
+        retour
+    }
+
+    pub(crate) fn finir(mut plateau: Plateau) -> Plateau {
+        plateau.finir_tour();
+        plateau
     }
 
-    fn realiser_tour(mut process: Vec<Rc<dyn State>>) -> Vec<EndTurn> {
-        let mut retour: Vec<EndTurn> = Vec::new();
+    // Toutes les combinaisons d'actions de `owner`, une par racine; le tour n'est pas terminé.
+    pub(crate) fn realiser_tour(
+        mut process: Vec<Plateau>,
+        owner: Owner,
+        elagage: Elagage,
+    ) -> Vec<Plateau> {
+        let mut retour: Vec<Plateau> = Vec::new();
         while !process.is_empty() {
-            let (finis, encore) = process
+            let (finis, encore): (Vec<_>, Vec<_>) = process
                 .into_iter()
-                .filter_map(|s| {
-                    Some((
-                        s.clone(),
-                        s.action_set().get(Owner::Me).iter().next().cloned()?,
-                    ))
+                .filter_map(|p| {
+                    let root_id = *p.a_jouer(owner).first()?;
+                    Some(generer_step(&p, root_id, elagage).collect::<Vec<_>>())
                 })
-                .flat_map(|(s, root_id)| generer_step(s, root_id))
-                .partition(|s| s.action_set().get(Owner::Me).is_empty());
+                .flatten()
+                .partition(|p| p.a_jouer(owner).is_empty());
             process = encore;
-            retour.extend(finis.into_iter().map(EndTurn::new));
+            retour.extend(finis);
         }
         retour
     }
 
-    fn generer_step(state: Rc<dyn State>, root_id: Id) -> impl Iterator<Item = Rc<dyn State>> {
-        let wait_step = Rc::new(WaitStep::new(state.clone(), Wait { root_id })) as Rc<dyn State>;
-        let spore_state = state.clone();
-        let spores = state
+    /// Résout en simultané le tour de `joue` depuis `avant`: l'ennemi ne voit pas nos coups.
+    pub fn repondre(
+        avant: &Plateau,
+        joue: Plateau,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> WeightedState {
+        let strategie = managing.reponse_ennemie();
+        let reponses: Vec<WeightedState> =
+            generer_reponses(avant, &joue, strategie, managing.rng())
+                .into_iter()
+                .map(|plateau| juger(plateau, evaluateur))
+                .collect();
+        let choisie = match strategie {
+            ReponseEnnemie::Ignorer => None,
+            ReponseEnnemie::PireCas(_) => reponses
+                .into_iter()
+                .min_by(|a, b| a.score.total_cmp(&b.score)),
+            ReponseEnnemie::Echantillon(_) => {
+                let nb = f64::from(u32::try_from(reponses.len()).unwrap_or(u32::MAX));
+                let moyenne = reponses.iter().map(|w| w.score).sum::<f64>() / nb;
+                // Le plateau rendu est celui dont la note est la plus proche de la moyenne.
+                let ecart = |w: &WeightedState| (w.score - moyenne).abs();
+                reponses
+                    .into_iter()
+                    .min_by(|a, b| ecart(a).total_cmp(&ecart(b)))
+                    .map(|w| WeightedState {
+                        state: w.state,
+                        score: moyenne,
+                    })
+            }
+        };
+        choisie.unwrap_or_else(|| juger(finir(joue), evaluateur))
+    }
+
+    fn generer_reponses(
+        avant: &Plateau,
+        joue: &Plateau,
+        strategie: ReponseEnnemie,
+        rng: &mut StdRng,
+    ) -> Vec<Plateau> {
+        let nos_coups = joue
+            .planification()
+            .take_content()
+            .pop()
+            .unwrap_or_default();
+        let racines: Vec<Id> = avant.a_jouer(Owner::Ennemy).to_vec();
+        let mut reponses: Vec<Plateau> = Vec::with_capacity(strategie.nb_reponses());
+        for indice in 0..strategie.nb_reponses() {
+            let mut stock = *avant.ressource().get(Owner::Ennemy);
+            let mut leurs_coups: Vec<Decision> = Vec::with_capacity(racines.len());
+            for root_id in racines.iter() {
+                let (mut attaques, mut autres) = coups_ennemis(avant, joue, *root_id);
+                let coups: Vec<Decision> = match strategie {
+                    ReponseEnnemie::PireCas(_) => {
+                        autres.shuffle(rng);
+                        attaques.into_iter().skip(indice).chain(autres).collect()
+                    }
+                    _ => {
+                        attaques.append(&mut autres);
+                        attaques.shuffle(rng);
+                        attaques
+                    }
+                };
+                let coup = coups
+                    .into_iter()
+                    .find(|coup| stock >= prix(coup))
+                    .unwrap_or(Decision::Wait);
+                stock = stock - prix(&coup);
+                leurs_coups.push(coup);
+            }
+            let mut reponse = avant.clone();
+            reponse.resoudre(OwnerMap::new(nos_coups.clone(), leurs_coups));
+            reponses.push(reponse);
+        }
+        reponses
+    }
+
+    fn prix(coup: &Decision) -> Ressource {
+        match coup {
+            Decision::Wait => Ressource::default(),
+            Decision::Grow(grow) => grow.organe_type.prix(),
+            Decision::Spore(_) => Ressource::new(1, 1, 1, 1),
+        }
+    }
+
+    // Les coups d'une racine ennemie, jouables sur `avant`: les tentacles qui visent un de nos
+    // organes de `apercu`, puis le reste.
+    fn coups_ennemis(
+        avant: &dyn State,
+        apercu: &dyn State,
+        root_id: Id,
+    ) -> (Vec<Decision>, Vec<Decision>) {
+        let (attaques, mut autres): (Vec<Decision>, Vec<Decision>) =
+            grow_candidates_triees(avant, root_id)
+                .into_iter()
+                .flat_map(|(coord, GrowCandidate { parent_id })| generer_grow(coord, parent_id))
+                .map(Decision::Grow)
+                .partition(|coup| match coup {
+                    Decision::Grow(grow) if grow.organe_type == OrganeType::Tentacle => matches!(
+                        apercu.en_face(grow.coord, grow.direction).map(|c| c.entity),
+                        Some(Entity::Organe(org)) if org.owner == Owner::Me
+                    ),
+                    _ => false,
+                });
+        autres.extend(
+            spores_triees(avant, root_id)
+                .into_iter()
+                .map(Decision::Spore),
+        );
+        autres.push(Decision::Wait);
+        (attaques, autres)
+    }
+
+    // Les candidats sortent de `HashMap`: on les trie pour que la génération soit reproductible.
+    fn grow_candidates_triees(state: &dyn State, root_id: Id) -> Vec<(Coord, GrowCandidate)> {
+        let mut candidates: Vec<(Coord, GrowCandidate)> =
+            state.grow_candidate(root_id).into_iter().collect();
+        candidates
+            .sort_by_key(|(coord, candidate)| (coord.y, coord.x, candidate.parent_id.get_num()));
+        candidates
+    }
+
+    fn spores_triees(state: &dyn State, root_id: Id) -> Vec<Spore> {
+        let mut spores: Vec<Spore> = state
             .spore_candidate(root_id)
             .into_iter()
-            .map(|spore_candidante| Spore {
-                coord: spore_candidante.coord,
-                parent_id: spore_candidante.parent_id,
+            .map(|candidate| Spore {
+                coord: candidate.coord,
+                parent_id: candidate.parent_id,
             })
-            .filter_map(move |spore| SporeStep::try_new(spore_state.clone(), spore))
-            .map(|s| Rc::new(s) as Rc<dyn State>);
+            .collect();
+        spores.sort_by_key(|spore| (spore.coord.y, spore.coord.x, spore.parent_id.get_num()));
+        spores
+    }
 
-        state
-            .grow_candidate(root_id)
+    pub(crate) fn jouer_coup(plateau: &mut Plateau, root_id: Id, coup: Decision) -> bool {
+        match coup {
+            Decision::Wait => {
+                plateau.wait(root_id);
+                true
+            }
+            Decision::Grow(grow) => plateau.grow(grow),
+            Decision::Spore(spore) => plateau.spore(spore),
+        }
+    }
+
+    pub(crate) fn generer_step(
+        plateau: &Plateau,
+        root_id: Id,
+        elagage: Elagage,
+    ) -> impl Iterator<Item = Plateau> + '_ {
+        let distances = elagage.eloignement.then(|| plateau.distances());
+        let economie = elagage
+            .famine
+            .then(|| Economie::de(plateau, root_id.get_owner()));
+        coups_possibles(plateau, root_id)
+            .into_iter()
+            .filter(move |coup| match (coup, &distances) {
+                (Decision::Grow(grow), Some(distances)) => !s_eloigne(plateau, distances, grow),
+                _ => true,
+            })
+            .filter(move |coup| match (coup, &economie) {
+                (Decision::Grow(grow), Some(economie)) => !affame(economie, grow),
+                _ => true,
+            })
+            .filter(move |coup| match coup {
+                Decision::Grow(grow) if elagage.menace => {
+                    !plateau.est_menacee(root_id.get_owner(), grow.coord)
+                }
+                _ => true,
+            })
+            .filter_map(move |coup| {
+                let mut suivant = plateau.clone();
+                jouer_coup(&mut suivant, root_id, coup).then_some(suivant)
+            })
+    }
+
+    // Une pousse s'éloigne si sa case est plus loin de toute protéine et de tout organe adverse
+    // que la meilleure case autour du parent. Les sporers visent loin et ne sont jamais élagués,
+    // pas plus que les cases au contact d'un objectif.
+    fn s_eloigne(state: &dyn State, distances: &Distances, grow: &Grow) -> bool {
+        let owner = grow.parent_id.get_owner();
+        let objectif = |coord: Coord| {
+            [
+                distances.vers_proteine(owner, coord),
+                distances.owner(owner.switch_side(), coord),
+            ]
+            .into_iter()
+            .flatten()
+            .min()
+        };
+        let Some(parent) = state.get_by_id(grow.parent_id) else {
+            return false;
+        };
+        let autour_du_parent = Direction::all()
+            .into_iter()
+            .filter_map(|direction| parent.coord.decaler(direction))
+            .filter_map(objectif)
+            .min();
+        match (objectif(grow.coord), autour_du_parent) {
+            _ if grow.organe_type == OrganeType::Sporer => false,
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(distance), Some(meilleure)) => distance > 1 && distance > meilleure,
+        }
+    }
+
+    fn affame(economie: &Economie, grow: &Grow) -> bool {
+        if grow.organe_type == OrganeType::Harvester {
+            return false;
+        }
+        let Some(stock) = economie.stock.checked_sub(grow.organe_type.prix()) else {
+            return false;
+        };
+        let apres = Economie { stock, ..*economie };
+        apres.famines().len() > economie.famines().len()
+    }
+
+    // Les coups abordables d'une racine, pousses puis WAIT puis spores, dans un ordre stable.
+    pub(crate) fn coups_possibles(state: &dyn State, root_id: Id) -> Vec<Decision> {
+        let ressource = *state.ressource().get(root_id.get_owner());
+        let abordable = |prix: Ressource| ressource >= prix;
+        let grows = grow_candidates_triees(state, root_id)
             .into_iter()
             .flat_map(|(coord, GrowCandidate { parent_id })| generer_grow(coord, parent_id))
-            .filter_map(move |grow| GrowStep::try_new(state.clone(), grow))
-            .map(|s| Rc::new(s) as Rc<dyn State>)
-            .chain(std::iter::once(wait_step))
+            .filter(|grow| abordable(grow.organe_type.prix()))
+            .map(Decision::Grow);
+        let spores = spores_triees(state, root_id)
+            .into_iter()
+            .filter(|_| abordable(Ressource::new(1, 1, 1, 1)))
+            .map(Decision::Spore);
+        grows
+            .chain(std::iter::once(Decision::Wait))
             .chain(spores)
+            .collect()
     }
 
     fn generer_grow(coord: Coord, parent_id: Id) -> impl Iterator<Item = Grow> {
@@ -131,46 +635,93 @@ pub mod ai {
         })
     }
 
-    pub fn juger(state: Rc<dyn State>) -> WeightedState {
-        let planification = state.planification().take_content();
-        let planifiaction_len = planification.len();
-        let wait_le_plus_tard_possible: usize = state
-            .planification()
-            .take_content()
-            .into_iter()
-            .enumerate()
-            .flat_map(|(i, v)| v.into_iter().map(move |d| (i, d)))
-            .map(|(i, d)| match d {
-                Decision::Wait if i > 5 => 0,
-                Decision::Wait => i.abs_diff(planifiaction_len) * 5,
-                _ => 0,
-            })
-            .sum();
+    pub fn juger(state: Plateau, evaluateur: &dyn Evaluator) -> WeightedState {
+        let score = evaluateur.evaluer(&state);
+        WeightedState { state, score }
+    }
 
-        let nb_harvesting = state.harvesting().len();
-        let note_nb_harvesting = nb_harvesting.min(3) * 4;
+    /// Comment l'ennemi répond à nos actions pendant la planification.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ReponseEnnemie {
+        Ignorer,
+        /// Garde la pire de `n` réponses, les attaques de tentacle étant essayées en premier.
+        PireCas(usize),
+        /// Moyenne sur `n` réponses tirées au hasard.
+        Echantillon(usize),
+    }
 
-        let resources = state.ressource();
-        let note_resources = Protein::all()
-            .into_iter()
-            .filter(|p| resources.get(Owner::Me).get(p) != 0)
-            .count();
-        let nb_ami = state.nb_organe(Owner::Me) * 3;
-        let nb_ennemi = state.nb_organe(Owner::Ennemy) * 3;
+    impl ReponseEnnemie {
+        fn nb_reponses(&self) -> usize {
+            match self {
+                ReponseEnnemie::Ignorer => 0,
+                ReponseEnnemie::PireCas(n) | ReponseEnnemie::Echantillon(n) => *n,
+            }
+        }
+    }
+
+    /// Comment `planifier` retient les plateaux d'une itération à l'autre.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SearchStrategy {
+        /// Tirage sans remise, pondéré par les notes de `juger` converties selon `Ponderation`.
+        Aleatoire,
+        /// Les meilleures notes, départagés par l'ordre de génération: reproductible.
+        Faisceau,
+        /// Recherche arborescente Monte-Carlo, voir `mcts::planifier_mcts`.
+        Mcts(Rollout),
+        /// Arbres découplés à coups simultanés, voir `smitsimax::planifier_smitsimax`.
+        Smitsimax(Decouplage),
+        /// Population de plans sur plusieurs tours, voir `evolution::planifier_evolution`.
+        Evolution,
+    }
+
+    /// Conversion des notes en poids de tirage, tous strictement positifs.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum Ponderation {
+        /// `exp((note - meilleure) / température)`.
+        Softmax(f64),
+        /// Le poids ne dépend que du rang: la pire note pèse 1, la meilleure `n`.
+        Rang,
+    }
+
+    impl Ponderation {
+        pub fn poids(&self, scores: &[f64]) -> Vec<f64> {
+            match self {
+                Ponderation::Softmax(temperature) => {
+                    let meilleur = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                    let temperature = temperature.max(f64::MIN_POSITIVE);
+                    scores
+                        .iter()
+                        .map(|s| ((s - meilleur) / temperature).exp().max(f64::MIN_POSITIVE))
+                        .collect()
+                }
+                Ponderation::Rang => {
+                    let mut ordre: Vec<usize> = (0..scores.len()).collect();
+                    ordre.sort_by(|a, b| scores[*a].total_cmp(&scores[*b]));
+                    let mut poids = vec![0.0; scores.len()];
+                    for (rang, indice) in ordre.into_iter().enumerate() {
+                        poids[indice] = f64::from(u32::try_from(rang + 1).unwrap_or(u32::MAX));
+                    }
+                    poids
+                }
+            }
+        }
+    }
 
-        let weight = u32::try_from(1 + note_nb_harvesting + note_resources + nb_ami)
-            .unwrap_or(u32::MAX)
-            .saturating_sub(u32::try_from(nb_ennemi).unwrap_or(u32::MAX))
-            .saturating_add(5)
-            .saturating_sub(u32::try_from(wait_le_plus_tard_possible).unwrap_or(u32::MAX))
-            .clamp(0, u32::MAX);
-        WeightedState { state, weight }
+    /// Coups que `generer_step` écarte avant de les jouer.
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct Elagage {
+        /// Les pousses qui s'éloignent de toutes les protéines et des organes adverses.
+        pub eloignement: bool,
+        /// Les pousses, hors harvesters, qui épuisent une protéine dont on n'a aucun revenu.
+        pub famine: bool,
+        /// Les pousses sur une case en face d'un tentacle adverse.
+        pub menace: bool,
     }
 
     #[derive(Debug, Clone)]
     pub struct WeightedState {
-        pub state: Rc<dyn State>,
-        pub weight: u32,
+        pub state: Plateau,
+        pub score: f64,
     }
 
     pub struct Managing {
@@ -182,6 +733,18 @@ pub mod ai {
         tour_nb: u8,
         rng: StdRng,
         nb_to_choose: usize,
+        reponse_ennemie: ReponseEnnemie,
+        search_strategy: SearchStrategy,
+        ponderation: Ponderation,
+        elagage: Elagage,
+        // Meilleur plan du tour précédent, décalé d'un tour à chaque `next_turn`.
+        plan_precedent: Option<Planification>,
+    }
+
+    impl Default for Managing {
+        fn default() -> Self {
+            Self::new()
+        }
     }
 
     impl Managing {
@@ -193,8 +756,15 @@ pub mod ai {
                 end_offset: Duration::from_millis(10),
                 nb_max_iteration: 4,
                 tour_nb: 0,
-                rng: rand::rngs::StdRng::seed_from_u64(81),
+                rng: StdRng::seed_from_u64(81),
                 nb_to_choose: 30,
+                reponse_ennemie: ReponseEnnemie::Ignorer,
+                search_strategy: SearchStrategy::Aleatoire,
+                // Une récolte de moins (4 points) pèse 0,14 fois le meilleur plan; à 5.0 elle pesait
+                // encore 0,45 et `simple_va_harvester` ratait au-delà de sa tolérance.
+                ponderation: Ponderation::Softmax(2.0),
+                elagage: Elagage::default(),
+                plan_precedent: None,
             }
         }
 
@@ -213,6 +783,26 @@ pub mod ai {
             self
         }
 
+        pub fn with_reponse_ennemie(mut self, reponse_ennemie: ReponseEnnemie) -> Self {
+            self.reponse_ennemie = reponse_ennemie;
+            self
+        }
+
+        pub fn with_search_strategy(mut self, search_strategy: SearchStrategy) -> Self {
+            self.search_strategy = search_strategy;
+            self
+        }
+
+        pub fn with_ponderation(mut self, ponderation: Ponderation) -> Self {
+            self.ponderation = ponderation;
+            self
+        }
+
+        pub fn with_elagage(mut self, elagage: Elagage) -> Self {
+            self.elagage = elagage;
+            self
+        }
+
         pub fn restart(&mut self) {
             self.debut = Instant::now();
         }
@@ -239,85 +829,1284 @@ pub mod ai {
             self.nb_to_choose
         }
 
+        pub fn reponse_ennemie(&self) -> ReponseEnnemie {
+            self.reponse_ennemie
+        }
+
+        pub fn search_strategy(&self) -> SearchStrategy {
+            self.search_strategy
+        }
+
+        pub fn ponderation(&self) -> Ponderation {
+            self.ponderation
+        }
+
+        pub fn elagage(&self) -> Elagage {
+            self.elagage
+        }
+
+        pub fn garder_plan(&mut self, plan: Planification) {
+            self.plan_precedent = Some(plan);
+        }
+
+        pub fn plan_precedent(&self) -> Option<&Planification> {
+            self.plan_precedent.as_ref()
+        }
+
         pub fn next_turn(&mut self) {
             self.tour_nb = self.tour_nb.saturating_add(1);
+            self.plan_precedent = self.plan_precedent.take().map(Planification::decaler);
         }
     }
 }
 
-pub mod state {
+pub mod evaluation {
     use std::{
-        collections::{HashMap, HashSet},
-        fmt::{Debug, Display},
-        rc::Rc,
+        fmt::{self, Display, Formatter},
+        fs, io,
+        path::Path,
     };
 
-    use itertools::iproduct;
-
-    use super::{atome::*, decision::*, molecule::*};
+    use super::{
+        atome::*, decision::Decision, distance::Territoire, economie::Economie, state::State,
+    };
 
-    pub trait State: Debug {
-        fn planification(&self) -> Planification;
-        fn action_set(&self) -> OwnerMap<HashSet<Id>>;
-        fn max_id(&self) -> OwnerMap<Id>;
-        fn ressource(&self) -> OwnerMap<Ressource>;
-        fn get_by_coord(&self, coord: Coord) -> Option<Cell>;
-        fn get_by_id(&self, id: Id) -> Option<OrgWithCoord>;
-        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord>;
-        fn child_by_parent(&self, parent_id: Id) -> HashSet<Id>;
+    /// Note un plateau du point de vue de `Owner::Me`; plus c'est haut, mieux c'est. La note
+    /// peut être négative: deux mauvais plateaux restent comparables.
+    pub trait Evaluator {
+        fn evaluer(&self, state: &dyn State) -> f64;
+    }
 
-        fn roots(&self) -> OwnerMap<HashSet<Id>>;
-        fn attacking(&self) -> CoordMap<Attacking>;
-        fn harvesting(&self) -> CoordMap<Harvesting>;
-        fn empty_cell(&self) -> CoordMap<EmptyCell>;
-        fn protein(&self) -> CoordMap<Protein>;
+    /// Poids de l'évaluation par défaut, relus par `Poids::charger` au format `cle = valeur`.
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Poids {
+        pub socle: f64,
+        pub par_recolte: f64,
+        pub recoltes_max: f64,
+        pub par_proteine_en_stock: f64,
+        pub par_organe_ami: f64,
+        pub par_organe_ennemi: f64,
+        pub bonus: f64,
+        /// Pénalité d'un WAIT, multipliée par le nombre de tours restant après lui.
+        pub par_tour_de_wait: f64,
+        /// Les WAIT joués après ce tour ne sont plus pénalisés.
+        pub dernier_tour_penalise: f64,
+        /// Pénalité par pousse qui nous sépare de la protéine atteignable la plus proche.
+        pub par_case_vers_proteine: f64,
+        /// Par case de territoire d'avance sur l'ennemi, voir `distance::Territoire`.
+        pub par_case_controlee: f64,
+        /// Par protéine de territoire d'avance sur l'ennemi, chaque type compté à part.
+        pub par_proteine_controlee: f64,
+        /// Par protéine épuisée et sans revenu, voir `economie::Economie::famines`.
+        pub par_famine: f64,
+        /// Par organe ami en face d'un tentacle ennemi, donc perdu au prochain tour.
+        pub par_organe_menace: f64,
+        /// Par unité de revenu mangée par nos pousses, moins celle mangée à l'ennemi.
+        pub par_revenu_sacrifie: f64,
+    }
 
-        fn nb_organe(&self, owner: Owner) -> usize {
-            self.roots()
-                .get(owner)
-                .into_iter()
-                .map(|root_id| self.organes_by_root(root_id.clone()).len())
-                .sum()
+    impl Default for Poids {
+        fn default() -> Self {
+            Self {
+                socle: 1.0,
+                par_recolte: 4.0,
+                recoltes_max: 3.0,
+                par_proteine_en_stock: 1.0,
+                par_organe_ami: 3.0,
+                par_organe_ennemi: 3.0,
+                bonus: 5.0,
+                par_tour_de_wait: 5.0,
+                dernier_tour_penalise: 5.0,
+                par_case_vers_proteine: 0.0,
+                par_case_controlee: 0.0,
+                par_proteine_controlee: 0.0,
+                par_famine: 0.0,
+                par_organe_menace: 0.0,
+                par_revenu_sacrifie: 0.0,
+            }
         }
+    }
 
-        fn get_neighbour(&self, coor: Coord) -> [Option<Cell>; 4] {
-            Direction::all()
-                .map(|direction| coor.decaler(direction.clone()))
-                .map(|co| self.get_by_coord(co?))
+    #[derive(Debug)]
+    pub enum ErreurPoids {
+        Io(io::Error),
+        Ligne { ligne: usize, brut: String },
+        Cle { ligne: usize, cle: String },
+    }
+
+    impl Display for ErreurPoids {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self {
+                ErreurPoids::Io(erreur) => write!(f, "lecture des poids impossible: {erreur}"),
+                ErreurPoids::Ligne { ligne, brut } => {
+                    write!(f, "ligne {ligne}: `cle = nombre` attendu, lu {brut:?}")
+                }
+                ErreurPoids::Cle { ligne, cle } => write!(f, "ligne {ligne}: clé inconnue {cle:?}"),
+            }
         }
+    }
 
-        fn en_face(&self, coord: Coord, direction: Direction) -> Option<Cell> {
-            coord.decaler(direction).and_then(|c| self.get_by_coord(c))
+    impl Poids {
+        const CLES: [&'static str; 15] = [
+            "socle",
+            "par_recolte",
+            "recoltes_max",
+            "par_proteine_en_stock",
+            "par_organe_ami",
+            "par_organe_ennemi",
+            "bonus",
+            "par_tour_de_wait",
+            "dernier_tour_penalise",
+            "par_case_vers_proteine",
+            "par_case_controlee",
+            "par_proteine_controlee",
+            "par_famine",
+            "par_organe_menace",
+            "par_revenu_sacrifie",
+        ];
+
+        pub fn charger(chemin: impl AsRef<Path>) -> Result<Self, ErreurPoids> {
+            Self::lire(&fs::read_to_string(chemin).map_err(ErreurPoids::Io)?)
+        }
+
+        pub fn lire(texte: &str) -> Result<Self, ErreurPoids> {
+            let mut poids = Self::default();
+            for (indice, brut) in texte.lines().enumerate() {
+                let ligne = indice + 1;
+                let contenu = brut.split('#').next().unwrap_or_default().trim();
+                if contenu.is_empty() {
+                    continue;
+                }
+                let erreur = || ErreurPoids::Ligne {
+                    ligne,
+                    brut: brut.to_string(),
+                };
+                let (cle, valeur) = contenu.split_once('=').ok_or_else(erreur)?;
+                let valeur: f64 = valeur.trim().parse().map_err(|_| erreur())?;
+                let champ = poids.champ(cle.trim()).ok_or_else(|| ErreurPoids::Cle {
+                    ligne,
+                    cle: cle.trim().to_string(),
+                })?;
+                *champ = valeur;
+            }
+            Ok(poids)
+        }
+
+        fn champ(&mut self, cle: &str) -> Option<&mut f64> {
+            match cle {
+                "socle" => Some(&mut self.socle),
+                "par_recolte" => Some(&mut self.par_recolte),
+                "recoltes_max" => Some(&mut self.recoltes_max),
+                "par_proteine_en_stock" => Some(&mut self.par_proteine_en_stock),
+                "par_organe_ami" => Some(&mut self.par_organe_ami),
+                "par_organe_ennemi" => Some(&mut self.par_organe_ennemi),
+                "bonus" => Some(&mut self.bonus),
+                "par_tour_de_wait" => Some(&mut self.par_tour_de_wait),
+                "dernier_tour_penalise" => Some(&mut self.dernier_tour_penalise),
+                "par_case_vers_proteine" => Some(&mut self.par_case_vers_proteine),
+                "par_case_controlee" => Some(&mut self.par_case_controlee),
+                "par_proteine_controlee" => Some(&mut self.par_proteine_controlee),
+                "par_famine" => Some(&mut self.par_famine),
+                "par_organe_menace" => Some(&mut self.par_organe_menace),
+                "par_revenu_sacrifie" => Some(&mut self.par_revenu_sacrifie),
+                _ => None,
+            }
         }
+    }
 
-        fn fertile_cell(&self) -> CoordMap<Fertile> {
-            self.protein()
-                .into_iter()
-                .map(|(c, p)| (c, Some(p)))
-                .chain(self.empty_cell().keys().map(|c| (c.clone(), None)))
-                .map(|(coord, protein)| (coord, Fertile { coord, protein }))
-                .collect()
+    // Écrit les poids au format relu par `Poids::lire`.
+    impl Display for Poids {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            let mut poids = *self;
+            for cle in Self::CLES {
+                let valeur = poids.champ(cle).map_or(0.0, |v| *v);
+                writeln!(f, "{cle} = {valeur}")?;
+            }
+            Ok(())
         }
+    }
 
-        fn grow_candidate(&self, root_id: Id) -> CoordMap<GrowCandidate> {
-            let empty_or_prot: HashSet<Coord> = self.fertile_cell().keys().cloned().collect();
-            self.organes_by_root(root_id)
+    fn compter(nb: usize) -> f64 {
+        f64::from(u32::try_from(nb).unwrap_or(u32::MAX))
+    }
+
+    fn avance_territoriale(poids: &Poids, territoire: &Territoire) -> f64 {
+        let avance = |me: usize, ennemi: usize| compter(me) - compter(ennemi);
+        let cases = avance(
+            territoire.nb_cases(Owner::Me),
+            territoire.nb_cases(Owner::Ennemy),
+        );
+        let proteines: f64 = Protein::all()
+            .into_iter()
+            .map(|p| {
+                avance(
+                    territoire.nb_proteines(Owner::Me, p),
+                    territoire.nb_proteines(Owner::Ennemy, p),
+                )
+            })
+            .sum();
+        cases * poids.par_case_controlee + proteines * poids.par_proteine_controlee
+    }
+
+    impl Evaluator for Poids {
+        fn evaluer(&self, state: &dyn State) -> f64 {
+            let planification = state.planification().take_content();
+            let nb_tours = planification.len();
+            let penalite_wait: f64 = planification
                 .into_iter()
-                .flat_map(|org| {
-                    self.get_neighbour(org.coord)
-                        .into_iter()
-                        .filter_map(|c| c.clone())
-                        .filter(|c| empty_or_prot.contains(&c.coord))
-                        .map(move |c| (c.coord, GrowCandidate { parent_id: org.id }))
-                        .collect::<CoordMap<GrowCandidate>>()
-                })
-                .collect()
+                .enumerate()
+                .filter(|(i, _)| compter(*i) <= self.dernier_tour_penalise)
+                .flat_map(|(i, tour)| tour.into_iter().map(move |d| (i, d)))
+                .filter(|(_, decision)| *decision == Decision::Wait)
+                .map(|(i, _)| compter(i.abs_diff(nb_tours)) * self.par_tour_de_wait)
+                .sum();
+
+            let recoltes = compter(state.harvesting().get(Owner::Me).len()).min(self.recoltes_max);
+            let sacrifie = {
+                let total = |r: &Ressource| f64::from(r.total());
+                let sacrifie = state.revenu_sacrifie();
+                total(sacrifie.get(Owner::Me)) - total(sacrifie.get(Owner::Ennemy))
+            };
+            let ressources = state.ressource();
+            let en_stock = Protein::all()
+                .into_iter()
+                .filter(|p| ressources.get(Owner::Me).get(p) != 0)
+                .count();
+            let amis = compter(state.nb_organe(Owner::Me));
+            let ennemis = compter(state.nb_organe(Owner::Ennemy));
+            // Les parcours en largeur ne sont payés que si leurs poids servent.
+            let vers_proteine = match self.par_case_vers_proteine {
+                0.0 => 0.0,
+                _ => state
+                    .distances()
+                    .proteine_la_plus_proche(Owner::Me)
+                    .map_or(0.0, f64::from),
+            };
+            let territoire = match (self.par_case_controlee, self.par_proteine_controlee) {
+                (0.0, 0.0) => 0.0,
+                _ => avance_territoriale(self, &state.territoire()),
+            };
+            let famines = match self.par_famine {
+                0.0 => 0.0,
+                _ => compter(Economie::de(state, Owner::Me).famines().len()),
+            };
+            let menaces = match self.par_organe_menace {
+                0.0 => 0.0,
+                _ => {
+                    let menacees = state.menaces(Owner::Me);
+                    let touches = state
+                        .roots()
+                        .get(Owner::Me)
+                        .iter()
+                        .flat_map(|root_id| state.organes_by_root(*root_id))
+                        .filter(|org| menacees.contains(&org.coord))
+                        .count();
+                    compter(touches)
+                }
+            };
+
+            self.socle
+                + recoltes * self.par_recolte
+                + compter(en_stock) * self.par_proteine_en_stock
+                + amis * self.par_organe_ami
+                - ennemis * self.par_organe_ennemi
+                + self.bonus
+                - penalite_wait
+                - vers_proteine * self.par_case_vers_proteine
+                + territoire
+                - famines * self.par_famine
+                - menaces * self.par_organe_menace
+                - sacrifie * self.par_revenu_sacrifie
         }
+    }
+}
 
-        fn spore_candidate(&self, root_id: Id) -> HashSet<SporeCandidate> {
-            let empty_or_prot: HashSet<Coord> = self.fertile_cell().keys().cloned().collect();
-            let mut candidates: HashSet<SporeCandidate> = HashSet::new();
-            for org in self
+pub mod distance {
+    use std::collections::{HashMap, HashSet, VecDeque};
+
+    use super::{atome::*, molecule::*, state::State};
+
+    pub type Champ = Vec<Option<u16>>;
+
+    /// Distances en nombre de pousses; murs, organes et cases menacées bloquent.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Distances {
+        dimension: Dimension,
+        // Depuis tous les organes du joueur.
+        par_owner: OwnerMap<Champ>,
+        // Depuis les organes d'une seule racine.
+        par_racine: HashMap<Id, Champ>,
+        // Depuis les protéines, avec les cases praticables pour le joueur.
+        vers_proteines: OwnerMap<Champ>,
+    }
+
+    impl Distances {
+        pub fn calculer<S: State + ?Sized>(state: &S) -> Self {
+            let dimension = state.dimension();
+            let praticables = praticables(state);
+            let roots = state.roots();
+            let mut par_racine: HashMap<Id, Champ> = HashMap::new();
+            let mut par_owner: OwnerMap<Champ> = OwnerMap::default();
+            for owner in [Owner::Me, Owner::Ennemy] {
+                let mut tous: Vec<Coord> = Vec::new();
+                for root_id in roots.get(owner) {
+                    let sources: Vec<Coord> = state
+                        .organes_by_root(*root_id)
+                        .into_iter()
+                        .map(|org| org.coord)
+                        .collect();
+                    let champ = parcourir(dimension, &sources, praticables.get(owner));
+                    par_racine.insert(*root_id, champ);
+                    tous.extend(sources);
+                }
+                *par_owner.get_mut(owner) = parcourir(dimension, &tous, praticables.get(owner));
+            }
+            let proteines: Vec<Coord> = state.protein().into_keys().collect();
+            let vers_proteines = OwnerMap::new(
+                parcourir(dimension, &proteines, praticables.get(Owner::Me)),
+                parcourir(dimension, &proteines, praticables.get(Owner::Ennemy)),
+            );
+            Self {
+                dimension,
+                par_owner,
+                par_racine,
+                vers_proteines,
+            }
+        }
+
+        /// Nombre de pousses pour que `owner` atteigne `coord`, 0 sur ses propres organes.
+        pub fn owner(&self, owner: Owner, coord: Coord) -> Option<u16> {
+            self.lire(self.par_owner.get(owner), coord)
+        }
+
+        pub fn racine(&self, root_id: Id, coord: Coord) -> Option<u16> {
+            self.lire(self.par_racine.get(&root_id)?, coord)
+        }
+
+        /// Nombre de cases praticables pour `owner` entre `coord` et la protéine la plus proche.
+        pub fn vers_proteine(&self, owner: Owner, coord: Coord) -> Option<u16> {
+            self.lire(self.vers_proteines.get(owner), coord)
+        }
+
+        /// Distance entre les organes de `owner` et la protéine atteignable la plus proche.
+        pub fn proteine_la_plus_proche(&self, owner: Owner) -> Option<u16> {
+            self.par_owner
+                .get(owner)
+                .iter()
+                .zip(self.vers_proteines.get(owner))
+                .filter(|(_, vers)| **vers == Some(0))
+                .filter_map(|(depuis, _)| *depuis)
+                .min()
+        }
+
+        fn lire(&self, champ: &Champ, coord: Coord) -> Option<u16> {
+            if !self.dimension.contient(coord) {
+                return None;
+            }
+            let index =
+                usize::from(coord.y) * usize::from(self.dimension.width) + usize::from(coord.x);
+            champ.get(index).copied().flatten()
+        }
+    }
+
+    fn praticables<S: State + ?Sized>(state: &S) -> OwnerMap<HashSet<Coord>> {
+        let praticables =
+            |owner: Owner| -> HashSet<Coord> { state.fertile_cell(owner).into_keys().collect() };
+        OwnerMap::new(praticables(Owner::Me), praticables(Owner::Ennemy))
+    }
+
+    fn organes<S: State + ?Sized>(state: &S, owner: Owner) -> Vec<Coord> {
+        state
+            .roots()
+            .get(owner)
+            .iter()
+            .flat_map(|root_id| state.organes_by_root(*root_id))
+            .map(|org| org.coord)
+            .collect()
+    }
+
+    /// Diagramme de Voronoï des deux joueurs; une case atteinte au même tour reste neutre.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Territoire {
+        dimension: Dimension,
+        controle: Vec<Option<Owner>>,
+        cases: OwnerMap<usize>,
+        proteines: OwnerMap<HashMap<Protein, usize>>,
+    }
+
+    impl Territoire {
+        pub fn calculer<S: State + ?Sized>(state: &S) -> Self {
+            let dimension = state.dimension();
+            let largeur = usize::from(dimension.width);
+            let index = |coord: Coord| usize::from(coord.y) * largeur + usize::from(coord.x);
+            let praticables = praticables(state);
+            let mut controle: Vec<Option<Owner>> =
+                vec![None; largeur * usize::from(dimension.height)];
+            let mut atteinte = vec![false; controle.len()];
+            let mut front: Vec<(Coord, Owner)> = Vec::new();
+            for owner in [Owner::Me, Owner::Ennemy] {
+                for coord in organes(state, owner) {
+                    atteinte[index(coord)] = true;
+                    controle[index(coord)] = Some(owner);
+                    front.push((coord, owner));
+                }
+            }
+            while !front.is_empty() {
+                let mut revendiquees: HashMap<Coord, Option<Owner>> = HashMap::new();
+                for (coord, owner) in front {
+                    for voisin in Direction::all()
+                        .into_iter()
+                        .filter_map(|d| coord.decaler(d))
+                    {
+                        if !praticables.get(owner).contains(&voisin) || atteinte[index(voisin)] {
+                            continue;
+                        }
+                        revendiquees
+                            .entry(voisin)
+                            .and_modify(|deja| {
+                                if *deja != Some(owner) {
+                                    *deja = None;
+                                }
+                            })
+                            .or_insert(Some(owner));
+                    }
+                }
+                front = Vec::new();
+                for (coord, owner) in revendiquees {
+                    atteinte[index(coord)] = true;
+                    controle[index(coord)] = owner;
+                    front.extend(owner.map(|owner| (coord, owner)));
+                }
+            }
+
+            let mut cases: OwnerMap<usize> = OwnerMap::default();
+            let mut proteines: OwnerMap<HashMap<Protein, usize>> = OwnerMap::default();
+            for coord in dimension.coords() {
+                let Some(owner) = controle[index(coord)] else {
+                    continue;
+                };
+                *cases.get_mut(owner) += 1;
+                if let Some(Entity::Protein(protein)) = state.get_by_coord(coord).map(|c| c.entity)
+                {
+                    *proteines.get_mut(owner).entry(protein).or_default() += 1;
+                }
+            }
+            Self {
+                dimension,
+                controle,
+                cases,
+                proteines,
+            }
+        }
+
+        /// Le joueur qui atteint `coord` en premier, `None` si la case est neutre ou inaccessible.
+        pub fn controleur(&self, coord: Coord) -> Option<Owner> {
+            if !self.dimension.contient(coord) {
+                return None;
+            }
+            self.controle
+                [usize::from(coord.y) * usize::from(self.dimension.width) + usize::from(coord.x)]
+        }
+
+        /// Les cases contrôlées par `owner`, ses organes compris.
+        pub fn nb_cases(&self, owner: Owner) -> usize {
+            *self.cases.get(owner)
+        }
+
+        pub fn nb_proteines(&self, owner: Owner, protein: Protein) -> usize {
+            self.proteines
+                .get(owner)
+                .get(&protein)
+                .copied()
+                .unwrap_or_default()
+        }
+    }
+
+    // Parcours en largeur: les sources sont à 0 même si elles ne sont pas praticables.
+    fn parcourir(dimension: Dimension, sources: &[Coord], praticables: &HashSet<Coord>) -> Champ {
+        let largeur = usize::from(dimension.width);
+        let index = |coord: Coord| usize::from(coord.y) * largeur + usize::from(coord.x);
+        let mut champ: Champ = vec![None; largeur * usize::from(dimension.height)];
+        let mut file: VecDeque<Coord> = VecDeque::new();
+        for source in sources.iter().filter(|c| dimension.contient(**c)) {
+            if champ[index(*source)].is_none() {
+                champ[index(*source)] = Some(0);
+                file.push_back(*source);
+            }
+        }
+        while let Some(coord) = file.pop_front() {
+            let suivante = champ[index(coord)].unwrap_or_default().saturating_add(1);
+            for voisin in Direction::all()
+                .into_iter()
+                .filter_map(|d| coord.decaler(d))
+            {
+                if praticables.contains(&voisin) && champ[index(voisin)].is_none() {
+                    champ[index(voisin)] = Some(suivante);
+                    file.push_back(voisin);
+                }
+            }
+        }
+        champ
+    }
+}
+
+pub mod economie {
+    use super::{atome::*, state::State};
+
+    /// Stock et revenu par tour d'un joueur.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Economie {
+        pub stock: Ressource,
+        pub revenu: Ressource,
+    }
+
+    impl Economie {
+        pub fn de<S: State + ?Sized>(state: &S, owner: Owner) -> Self {
+            let revenu = state
+                .harvesting()
+                .get(owner)
+                .values()
+                .fold(Ressource::default(), |revenu, harvesting| {
+                    revenu.ajout_1(harvesting.protein)
+                });
+            Self {
+                stock: *state.ressource().get(owner),
+                revenu,
+            }
+        }
+
+        /// Le stock dans `tours` tours si rien n'est dépensé.
+        pub fn projeter(&self, tours: u8) -> Ressource {
+            self.stock + self.revenu * u32::from(tours)
+        }
+
+        /// Combien de fois on peut payer `prix` avec le stock actuel.
+        pub fn nb_abordables(&self, prix: Ressource) -> u32 {
+            Protein::all()
+                .into_iter()
+                .filter(|p| prix.get(p) > 0)
+                .map(|p| self.stock.get(&p) / prix.get(&p))
+                .min()
+                .unwrap_or(u32::MAX)
+        }
+
+        /// Tours d'attente avant de payer `prix`; `None` si une protéine manque sans revenu.
+        pub fn tours_avant(&self, prix: Ressource) -> Option<u32> {
+            Protein::all()
+                .into_iter()
+                .map(|p| {
+                    let manque = prix.get(&p).saturating_sub(self.stock.get(&p));
+                    match (manque, self.revenu.get(&p)) {
+                        (0, _) => Some(0),
+                        (_, 0) => None,
+                        (manque, revenu) => Some(manque.div_ceil(revenu)),
+                    }
+                })
+                .try_fold(0, |pire, tours| Some(pire.max(tours?)))
+        }
+
+        /// Nombre de tours avant de pouvoir payer un organe de plus que maintenant.
+        pub fn prochain(&self, organe_type: OrganeType) -> Option<u32> {
+            let prix = organe_type.prix();
+            let nb = self.nb_abordables(prix).checked_add(1)?;
+            self.tours_avant(prix.checked_mul(nb)?)
+        }
+
+        /// Les protéines épuisées et sans revenu.
+        pub fn famines(&self) -> Vec<Protein> {
+            Protein::all()
+                .into_iter()
+                .filter(|p| self.stock.get(p) == 0 && self.revenu.get(p) == 0)
+                .collect()
+        }
+
+        /// Les types d'organe que l'on ne pourra jamais payer sans nouvelle récolte.
+        pub fn organes_bloques(&self) -> Vec<OrganeType> {
+            [
+                OrganeType::Basic,
+                OrganeType::Harvester,
+                OrganeType::Tentacle,
+                OrganeType::Sporer,
+            ]
+            .into_iter()
+            .filter(|organe_type| self.tours_avant(organe_type.prix()).is_none())
+            .collect()
+        }
+    }
+}
+
+pub mod mcts {
+    use rand::prelude::*;
+
+    use super::{ai::*, atome::*, evaluation::Evaluator, state::*};
+
+    /// Comment une simulation joue nos racines jusqu'à l'horizon.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Rollout {
+        Aleatoire,
+        /// Tirage pondéré par `juger` sur le plateau obtenu après chaque coup.
+        Heuristique,
+    }
+
+    const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+    struct Noeud {
+        plateau: Plateau,
+        profondeur: u8,
+        parent: Option<usize>,
+        enfants: Vec<usize>,
+        // `None` tant que les actions jointes du tour n'ont pas été générées.
+        a_developper: Option<Vec<Plateau>>,
+        visites: u32,
+        total: f64,
+        // Meilleure valeur simulée sous ce noeud: c'est elle qui désigne le plan retenu.
+        meilleur: f64,
+        complet: bool,
+    }
+
+    impl Noeud {
+        fn new(plateau: Plateau, profondeur: u8, parent: Option<usize>) -> Self {
+            Self {
+                plateau,
+                profondeur,
+                parent,
+                enfants: Vec::new(),
+                a_developper: None,
+                visites: 0,
+                total: 0.0,
+                meilleur: f64::MIN,
+                complet: false,
+            }
+        }
+
+        fn moyenne(&self) -> f64 {
+            self.total / f64::from(self.visites.max(1))
+        }
+    }
+
+    // Bornes des valeurs vues, pour ramener la moyenne d'un noeud dans [0, 1].
+    pub(crate) struct Bornes {
+        min: f64,
+        max: f64,
+    }
+
+    impl Bornes {
+        pub(crate) fn new() -> Self {
+            Self {
+                min: f64::MAX,
+                max: f64::MIN,
+            }
+        }
+
+        pub(crate) fn inclure(&mut self, valeur: f64) {
+            self.min = self.min.min(valeur);
+            self.max = self.max.max(valeur);
+        }
+
+        pub(crate) fn normaliser(&self, valeur: f64) -> f64 {
+            if self.max > self.min {
+                (valeur - self.min) / (self.max - self.min)
+            } else {
+                0.5
+            }
+        }
+    }
+
+    /// UCT sur des tours complets, chaque arête étant une action jointe de nos racines.
+    pub fn planifier_mcts(
+        state: &dyn State,
+        managing: &mut Managing,
+        rollout: Rollout,
+        evaluateur: &dyn Evaluator,
+    ) -> Planification {
+        let horizon = managing.iterations().end;
+        let mut arbre = vec![Noeud::new(Plateau::new(state), 0, None)];
+        let mut bornes = Bornes::new();
+        loop {
+            let feuille = descendre(&arbre, &bornes);
+            let noeud = developper(&mut arbre, feuille, horizon, managing, evaluateur);
+            let restants = horizon.saturating_sub(arbre[noeud].profondeur);
+            let valeur = simuler(
+                arbre[noeud].plateau.clone(),
+                restants,
+                rollout,
+                managing,
+                evaluateur,
+            );
+            bornes.inclure(valeur);
+            retropropager(&mut arbre, noeud, valeur);
+            if arbre[0].complet || managing.is_finished() {
+                break;
+            }
+        }
+
+        // On ne joue que nos coups: on suit la meilleure simulation plutôt que la plus visitée,
+        // les sous-arbres complets n'étant plus visités.
+        let mut courant = 0;
+        while let Some(suivant) = arbre[courant].enfants.iter().copied().max_by(|a, b| {
+            let (a, b) = (&arbre[*a], &arbre[*b]);
+            a.meilleur
+                .total_cmp(&b.meilleur)
+                .then(a.visites.cmp(&b.visites))
+        }) {
+            courant = suivant;
+        }
+        arbre[courant].plateau.planification()
+    }
+
+    fn descendre(arbre: &[Noeud], bornes: &Bornes) -> usize {
+        let mut courant = 0;
+        loop {
+            let noeud = &arbre[courant];
+            let developpe = matches!(&noeud.a_developper, Some(restants) if restants.is_empty());
+            if !developpe || noeud.complet {
+                return courant;
+            }
+            let log_parent = f64::from(noeud.visites.max(1)).ln();
+            let meilleur = noeud
+                .enfants
+                .iter()
+                .copied()
+                .filter(|enfant| !arbre[*enfant].complet)
+                .map(|enfant| {
+                    let e = &arbre[enfant];
+                    let visites = f64::from(e.visites.max(1));
+                    let uct = bornes.normaliser(e.moyenne())
+                        + EXPLORATION * (log_parent / visites).sqrt();
+                    (enfant, uct)
+                })
+                .max_by(|(_, a), (_, b)| a.total_cmp(b));
+            match meilleur {
+                Some((enfant, _)) => courant = enfant,
+                None => return courant,
+            }
+        }
+    }
+
+    // Ajoute un enfant à `indice`, ou renvoie `indice` s'il est à l'horizon ou sans coup.
+    fn developper(
+        arbre: &mut Vec<Noeud>,
+        indice: usize,
+        horizon: u8,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> usize {
+        if arbre[indice].profondeur >= horizon {
+            arbre[indice].complet = true;
+            return indice;
+        }
+        if arbre[indice].a_developper.is_none() {
+            let avant = arbre[indice].plateau.clone();
+            let mut candidats: Vec<WeightedState> =
+                realiser_tour(vec![avant.clone()], Owner::Me, managing.elagage())
+                    .into_iter()
+                    .map(|plateau| match managing.reponse_ennemie() {
+                        ReponseEnnemie::Ignorer => juger(finir(plateau), evaluateur),
+                        _ => repondre(&avant, plateau, managing, evaluateur),
+                    })
+                    .collect();
+            // Les mieux notés sont développés en premier, le reste est élagué.
+            candidats.sort_by(|a, b| a.score.total_cmp(&b.score));
+            let nb = candidats.len().saturating_sub(managing.nb_to_choose());
+            let plateaux = candidats.into_iter().skip(nb).map(|w| w.state).collect();
+            arbre[indice].a_developper = Some(plateaux);
+        }
+        let profondeur = arbre[indice].profondeur + 1;
+        match arbre[indice].a_developper.as_mut().and_then(Vec::pop) {
+            Some(plateau) => {
+                let enfant = arbre.len();
+                arbre.push(Noeud::new(plateau, profondeur, Some(indice)));
+                arbre[indice].enfants.push(enfant);
+                enfant
+            }
+            None => indice,
+        }
+    }
+
+    fn simuler(
+        mut plateau: Plateau,
+        tours: u8,
+        rollout: Rollout,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> f64 {
+        for _ in 0..tours {
+            while let Some(root_id) = plateau.a_jouer(Owner::Me).first().copied() {
+                let coups: Vec<Plateau> =
+                    generer_step(&plateau, root_id, managing.elagage()).collect();
+                let choisi = match rollout {
+                    Rollout::Aleatoire => coups.choose(managing.rng()),
+                    Rollout::Heuristique => {
+                        let scores: Vec<f64> = coups
+                            .iter()
+                            .map(|coup| juger(finir(coup.clone()), evaluateur).score)
+                            .collect();
+                        let poids = managing.ponderation().poids(&scores);
+                        let indices: Vec<usize> = (0..coups.len()).collect();
+                        indices
+                            .choose_weighted(managing.rng(), |i| poids[*i])
+                            .ok()
+                            .map(|i| &coups[*i])
+                    }
+                };
+                match choisi {
+                    Some(coup) => plateau = coup.clone(),
+                    None => break,
+                }
+            }
+            plateau.finir_tour();
+        }
+        juger(plateau, evaluateur).score
+    }
+
+    fn retropropager(arbre: &mut [Noeud], feuille: usize, valeur: f64) {
+        let mut courant = Some(feuille);
+        while let Some(indice) = courant {
+            let complet = match &arbre[indice].a_developper {
+                Some(restants) => {
+                    restants.is_empty() && arbre[indice].enfants.iter().all(|e| arbre[*e].complet)
+                }
+                None => false,
+            };
+            let noeud = &mut arbre[indice];
+            noeud.visites = noeud.visites.saturating_add(1);
+            noeud.total += valeur;
+            noeud.meilleur = noeud.meilleur.max(valeur);
+            noeud.complet = noeud.complet || complet;
+            courant = noeud.parent;
+        }
+    }
+}
+
+pub mod smitsimax {
+    use std::collections::HashMap;
+
+    use rand::prelude::*;
+
+    use super::{
+        ai::*, atome::*, decision::*, evaluation::Evaluator, mcts::Bornes, molecule::*, state::*,
+    };
+
+    /// Découpage des arbres de décision: aucun ne voit les choix des autres pendant la descente.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Decouplage {
+        /// Un arbre par joueur, dont chaque niveau est la décision d'une racine par id croissant.
+        ParJoueur,
+        /// Un arbre par racine, dont chaque niveau est un tour.
+        ParRacine,
+    }
+
+    const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+    type Cle = (Owner, Option<Id>);
+
+    #[derive(Default)]
+    struct Noeud {
+        enfants: HashMap<Decision, usize>,
+        visites: u32,
+        total: f64,
+    }
+
+    struct Arbre {
+        noeuds: Vec<Noeud>,
+    }
+
+    impl Arbre {
+        fn new() -> Self {
+            Self {
+                noeuds: vec![Noeud::default()],
+            }
+        }
+
+        // UCB1 parmi les coups légaux de la case courante; un coup jamais joué passe en premier.
+        fn choisir(
+            &mut self,
+            noeud: usize,
+            coups: &[Decision],
+            rng: &mut StdRng,
+        ) -> (Decision, usize) {
+            let parent = &self.noeuds[noeud];
+            let inexplores: Vec<Decision> = coups
+                .iter()
+                .filter(|coup| !parent.enfants.contains_key(coup))
+                .copied()
+                .collect();
+            let coup = match inexplores.choose(rng) {
+                Some(coup) => *coup,
+                None => {
+                    let log_parent = f64::from(parent.visites.max(1)).ln();
+                    let ucb = |coup: &Decision| {
+                        let enfant = &self.noeuds[parent.enfants[coup]];
+                        let visites = f64::from(enfant.visites.max(1));
+                        enfant.total / visites + EXPLORATION * (log_parent / visites).sqrt()
+                    };
+                    coups
+                        .iter()
+                        .max_by(|a, b| ucb(a).total_cmp(&ucb(b)))
+                        .copied()
+                        .unwrap_or(Decision::Wait)
+                }
+            };
+            let enfant = match self.noeuds[noeud].enfants.get(&coup) {
+                Some(enfant) => *enfant,
+                None => {
+                    let enfant = self.noeuds.len();
+                    self.noeuds.push(Noeud::default());
+                    self.noeuds[noeud].enfants.insert(coup, enfant);
+                    enfant
+                }
+            };
+            (coup, enfant)
+        }
+
+        fn plus_visite(&self, noeud: usize) -> Option<(Decision, usize)> {
+            self.noeuds[noeud]
+                .enfants
+                .iter()
+                .max_by_key(|(coup, enfant)| (self.noeuds[**enfant].visites, coup.to_command()))
+                .map(|(coup, enfant)| (*coup, *enfant))
+        }
+    }
+
+    /// Recherche à coups simultanés: chaque arbre choisit ses coups sans connaître ceux des
+    /// autres, le tour est joué des deux côtés par `Plateau::resoudre` et tous les arbres reçoivent
+    /// le même résultat, inversé pour l'ennemi.
+    pub fn planifier_smitsimax(
+        state: &dyn State,
+        managing: &mut Managing,
+        decouplage: Decouplage,
+        evaluateur: &dyn Evaluator,
+    ) -> Planification {
+        let horizon = managing.iterations().end;
+        let depart = Plateau::new(state);
+        let mut arbres: HashMap<Cle, Arbre> = HashMap::new();
+        let mut bornes = Bornes::new();
+        loop {
+            iterer(
+                &depart,
+                horizon,
+                decouplage,
+                &mut arbres,
+                &mut bornes,
+                managing.rng(),
+                evaluateur,
+            );
+            if managing.is_finished() {
+                break;
+            }
+        }
+
+        let racines = racines_triees(&depart, Owner::Me);
+        let mut positions: HashMap<Cle, usize> = HashMap::new();
+        let mut planification = Planification::default();
+        'tours: for _ in 0..horizon {
+            let mut tour: Vec<Decision> = Vec::new();
+            for root_id in racines.iter() {
+                let cle = cle(decouplage, *root_id);
+                let position = positions.entry(cle).or_insert(0);
+                let Some((coup, enfant)) = arbres.get(&cle).and_then(|a| a.plus_visite(*position))
+                else {
+                    break 'tours;
+                };
+                *position = enfant;
+                tour.push(coup);
+            }
+            planification = tour
+                .into_iter()
+                .fold(planification, |p, coup| p.add_decision(coup))
+                .new_turn();
+        }
+        planification
+    }
+
+    fn cle(decouplage: Decouplage, root_id: Id) -> Cle {
+        match decouplage {
+            Decouplage::ParJoueur => (root_id.get_owner(), None),
+            Decouplage::ParRacine => (root_id.get_owner(), Some(root_id)),
+        }
+    }
+
+    fn racines_triees(state: &dyn State, owner: Owner) -> Vec<Id> {
+        let mut racines: Vec<Id> = state.roots().get(owner).iter().copied().collect();
+        racines.sort_by_key(|id| id.get_num());
+        racines
+    }
+
+    fn iterer(
+        depart: &Plateau,
+        horizon: u8,
+        decouplage: Decouplage,
+        arbres: &mut HashMap<Cle, Arbre>,
+        bornes: &mut Bornes,
+        rng: &mut StdRng,
+        evaluateur: &dyn Evaluator,
+    ) {
+        let mut chemins: HashMap<Cle, Vec<usize>> = HashMap::new();
+        let mut plateau = depart.clone();
+        for _ in 0..horizon {
+            let mut decisions: OwnerMap<Vec<Decision>> = OwnerMap::default();
+            for owner in [Owner::Me, Owner::Ennemy] {
+                for root_id in racines_triees(&plateau, owner) {
+                    let cle = cle(decouplage, root_id);
+                    let chemin = chemins.entry(cle).or_insert_with(|| vec![0]);
+                    let noeud = chemin.last().copied().unwrap_or(0);
+                    let coups = coups_possibles(&plateau, root_id);
+                    let arbre = arbres.entry(cle).or_insert_with(Arbre::new);
+                    let (coup, enfant) = arbre.choisir(noeud, &coups, rng);
+                    chemin.push(enfant);
+                    decisions.get_mut(owner).push(coup);
+                }
+            }
+            plateau.resoudre(decisions);
+        }
+
+        let valeur = juger(plateau, evaluateur).score;
+        bornes.inclure(valeur);
+        let valeur = bornes.normaliser(valeur);
+        for (cle, chemin) in chemins {
+            let resultat = match cle.0 {
+                Owner::Me => valeur,
+                Owner::Ennemy => 1.0 - valeur,
+            };
+            if let Some(arbre) = arbres.get_mut(&cle) {
+                for noeud in chemin {
+                    let noeud = &mut arbre.noeuds[noeud];
+                    noeud.visites = noeud.visites.saturating_add(1);
+                    noeud.total += resultat;
+                }
+            }
+        }
+    }
+}
+
+pub mod evolution {
+    use rand::prelude::*;
+
+    use super::{ai::*, atome::*, decision::*, evaluation::Evaluator, state::*};
+
+    /// Les décisions de chaque tour, une par racine amie dans l'ordre des ids.
+    pub type Genome = Vec<Vec<Decision>>;
+
+    const ELITES: usize = 2;
+
+    /// Planificateur évolutionnaire à horizon glissant, parti du plan du tour précédent.
+    pub fn planifier_evolution(
+        state: &dyn State,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> Planification {
+        let horizon = usize::from(managing.iterations().end);
+        let depart = Plateau::new(state);
+        let taille = managing.nb_to_choose().max(ELITES + 1);
+
+        let mut population: Vec<(Genome, f64)> = Vec::with_capacity(taille);
+        let precedent = managing.plan_precedent().map(|p| p.clone().take_content());
+        for genome in precedent.into_iter().chain(std::iter::repeat(Vec::new())) {
+            if population.len() >= taille {
+                break;
+            }
+            population.push(noter(genome, &depart, horizon, managing, evaluateur));
+        }
+
+        loop {
+            population.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+            if managing.is_finished() {
+                break;
+            }
+            let mut suivante: Vec<(Genome, f64)> = population[..ELITES].to_vec();
+            while suivante.len() < taille {
+                let mut enfant = tournoi(&population, managing.rng()).clone();
+                muter(&mut enfant, managing.rng());
+                suivante.push(noter(enfant, &depart, horizon, managing, evaluateur));
+            }
+            population = suivante;
+        }
+
+        population
+            .into_iter()
+            .next()
+            .map(|(genome, _)| Planification::from_content(genome))
+            .unwrap_or_default()
+    }
+
+    fn tournoi<'a>(population: &'a [(Genome, f64)], rng: &mut StdRng) -> &'a Genome {
+        let a = &population[rng.gen_range(0..population.len())];
+        let b = &population[rng.gen_range(0..population.len())];
+        if a.1 >= b.1 {
+            &a.0
+        } else {
+            &b.0
+        }
+    }
+
+    // Rejoue le génome en remplaçant chaque décision illégale ou manquante par un coup légal
+    // tiré au hasard; le génome réparé est gardé avec sa note.
+    fn noter(
+        mut genome: Genome,
+        depart: &Plateau,
+        horizon: usize,
+        managing: &mut Managing,
+        evaluateur: &dyn Evaluator,
+    ) -> (Genome, f64) {
+        genome.resize(horizon, Vec::new());
+        let mut plateau = depart.clone();
+        for tour in genome.iter_mut() {
+            let avant = plateau.clone();
+            let racines = plateau.a_jouer(Owner::Me).to_vec();
+            let mut repare: Vec<Decision> = Vec::with_capacity(racines.len());
+            for (indice, root_id) in racines.into_iter().enumerate() {
+                let coups = coups_possibles(&plateau, root_id);
+                let voulu = tour
+                    .get(indice)
+                    .copied()
+                    .filter(|coup| coups.contains(coup));
+                let coup = voulu
+                    .or_else(|| coups.choose(managing.rng()).copied())
+                    .unwrap_or(Decision::Wait);
+                if jouer_coup(&mut plateau, root_id, coup) {
+                    repare.push(coup);
+                } else {
+                    plateau.wait(root_id);
+                    repare.push(Decision::Wait);
+                }
+            }
+            *tour = repare;
+            plateau = match managing.reponse_ennemie() {
+                ReponseEnnemie::Ignorer => finir(plateau),
+                _ => repondre(&avant, plateau, managing, evaluateur).state,
+            };
+        }
+        let score = juger(plateau, evaluateur).score;
+        (genome, score)
+    }
+
+    /// Mutation ponctuelle d'un coup: la forme du génome, tours et coups par tour, est gardée.
+    pub fn muter(genome: &mut Genome, rng: &mut StdRng) {
+        let Some(tour) = genome.choose_mut(rng) else {
+            return;
+        };
+        if tour.is_empty() {
+            return;
+        }
+        let indice = rng.gen_range(0..tour.len());
+        let direction = *Direction::all().choose(rng).unwrap_or(&Direction::N);
+        match (&mut tour[indice], rng.gen_range(0..4)) {
+            (Decision::Grow(grow), 0) => {
+                let types = [
+                    OrganeType::Basic,
+                    OrganeType::Harvester,
+                    OrganeType::Tentacle,
+                    OrganeType::Sporer,
+                ];
+                grow.organe_type = *types.choose(rng).unwrap_or(&grow.organe_type)
+            }
+            (Decision::Grow(grow), 1) => grow.direction = direction,
+            (Decision::Grow(grow), 2) => {
+                grow.coord = grow.coord.decaler(direction).unwrap_or(grow.coord)
+            }
+            (Decision::Spore(spore), 0..=2) => {
+                spore.coord = spore.coord.decaler(direction).unwrap_or(spore.coord)
+            }
+            (coup, _) => *coup = Decision::Wait,
+        }
+    }
+}
+
+pub mod state {
+    use std::{
+        cell::OnceCell,
+        collections::{HashMap, HashSet},
+        fmt::{Debug, Display},
+        rc::Rc,
+    };
+
+    use itertools::iproduct;
+
+    use super::{
+        atome::*,
+        decision::*,
+        distance::{Distances, Territoire},
+        molecule::*,
+        rendu::Rendu,
+    };
+
+    pub trait State: Debug {
+        fn planification(&self) -> Planification;
+        fn action_set(&self) -> OwnerMap<HashSet<Id>>;
+        fn max_id(&self) -> OwnerMap<Id>;
+        fn dimension(&self) -> Dimension;
+        fn ressource(&self) -> OwnerMap<Ressource>;
+        fn get_by_coord(&self, coord: Coord) -> Option<Cell>;
+        fn get_by_id(&self, id: Id) -> Option<OrgWithCoord>;
+        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord>;
+        fn child_by_parent(&self, parent_id: Id) -> HashSet<Id>;
+
+        fn roots(&self) -> OwnerMap<HashSet<Id>>;
+        fn attacking(&self) -> CoordMap<Attacking>;
+        /// Les récoltes de chaque joueur, une par case de protéine visée.
+        fn harvesting(&self) -> OwnerMap<CoordMap<Harvesting>>;
+        fn empty_cell(&self) -> CoordMap<EmptyCell>;
+        fn protein(&self) -> CoordMap<Protein>;
+
+        fn nb_organe(&self, owner: Owner) -> usize {
+            self.roots()
+                .get(owner)
+                .iter()
+                .map(|root_id| self.organes_by_root(*root_id).len())
+                .sum()
+        }
+
+        fn get_neighbour(&self, coor: Coord) -> [Option<Cell>; 4] {
+            Direction::all()
+                .map(|direction| coor.decaler(direction))
+                .map(|co| self.get_by_coord(co?))
+        }
+
+        fn en_face(&self, coord: Coord, direction: Direction) -> Option<Cell> {
+            coord.decaler(direction).and_then(|c| self.get_by_coord(c))
+        }
+
+        /// Les cases où `owner` peut pousser, hors de portée des tentacles adverses.
+        fn fertile_cell(&self, owner: Owner) -> CoordMap<Fertile> {
+            let menacees = self.menaces(owner);
+            self.protein()
+                .into_iter()
+                .map(|(c, p)| (c, Some(p)))
+                .chain(self.empty_cell().keys().map(|c| (*c, None)))
+                .filter(|(coord, _)| !menacees.contains(coord))
+                .map(|(coord, protein)| (coord, Fertile { coord, protein }))
+                .collect()
+        }
+
+        fn grow_candidate(&self, root_id: Id) -> CoordMap<GrowCandidate> {
+            let empty_or_prot: HashSet<Coord> = self
+                .fertile_cell(root_id.get_owner())
+                .keys()
+                .cloned()
+                .collect();
+            self.organes_by_root(root_id)
+                .into_iter()
+                .flat_map(|org| {
+                    self.get_neighbour(org.coord)
+                        .into_iter()
+                        .flatten()
+                        .filter(|c| empty_or_prot.contains(&c.coord))
+                        .map(move |c| (c.coord, GrowCandidate { parent_id: org.id }))
+                        .collect::<CoordMap<GrowCandidate>>()
+                })
+                .collect()
+        }
+
+        /// La spore survole les cases libres en face d'un tentacle adverse sans pouvoir s'y poser.
+        fn spore_candidate(&self, root_id: Id) -> HashSet<SporeCandidate> {
+            let empty_or_prot: HashSet<Coord> = self
+                .protein()
+                .into_keys()
+                .chain(self.empty_cell().into_keys())
+                .collect();
+            let menacees = self.menaces(root_id.get_owner());
+            let mut candidates: HashSet<SporeCandidate> = HashSet::new();
+            for org in self
                 .organes_by_root(root_id)
                 .into_iter()
                 .filter(|o| o.organe_type == OrganeType::Sporer)
@@ -329,10 +2118,12 @@ pub mod state {
                     };
                     current_coord = new_corr;
                     if empty_or_prot.contains(&current_coord) {
-                        candidates.insert(SporeCandidate {
-                            parent_id: org.id,
-                            coord: current_coord,
-                        });
+                        if !menacees.contains(&current_coord) {
+                            candidates.insert(SporeCandidate {
+                                parent_id: org.id,
+                                coord: current_coord,
+                            });
+                        }
                     } else {
                         break 'segment;
                     }
@@ -341,8 +2132,142 @@ pub mod state {
                     }
                 }
             }
-            candidates
-        }
+            candidates
+        }
+
+        /// Empreinte de Zobrist des cases et des ressources. Les ids et la planification n'y
+        /// entrent pas: deux ordres de pousse qui mènent au même plateau ont la même empreinte.
+        /// Chaque état la garde à jour dans un champ: l'appel ne rebalaye jamais le plateau.
+        fn zobrist(&self) -> u64;
+
+        /// Vrai si `coord` est en face d'un tentacle adverse de `owner`.
+        fn est_menacee(&self, owner: Owner, coord: Coord) -> bool {
+            Direction::all().into_iter().any(|direction| {
+                matches!(
+                    self.en_face(coord, direction).map(|c| c.entity),
+                    Some(Entity::Organe(org)) if org.owner.is_ennemy(owner)
+                        && org.organe_type == OrganeType::Tentacle
+                        && org.dir == direction.opposee()
+                )
+            })
+        }
+
+        /// Revenu par tour que chaque joueur perd si la protéine en `coord` est mangée.
+        fn revenu_perdu(&self, coord: Coord) -> OwnerMap<Ressource> {
+            let mut perdu: OwnerMap<Ressource> = OwnerMap::default();
+            let Some(Cell {
+                entity: Entity::Protein(protein),
+                ..
+            }) = self.get_by_coord(coord)
+            else {
+                return perdu;
+            };
+            for owner in [Owner::Me, Owner::Ennemy] {
+                let vise = Direction::all().into_iter().any(|direction| {
+                    matches!(
+                        self.en_face(coord, direction).map(|c| c.entity),
+                        Some(Entity::Organe(org)) if org.owner == owner
+                            && org.organe_type == OrganeType::Harvester
+                            && org.dir == direction.opposee()
+                    )
+                });
+                if vise {
+                    *perdu.get_mut(owner) = perdu.get(owner).ajout_1(protein);
+                }
+            }
+            perdu
+        }
+
+        /// Revenu par tour perdu par les protéines récoltées mangées pendant la planification.
+        fn revenu_sacrifie(&self) -> OwnerMap<Ressource> {
+            OwnerMap::default()
+        }
+
+        /// Les cases visées par les tentacles adverses de `owner`, vides ou non.
+        fn menaces(&self, owner: Owner) -> HashSet<Coord> {
+            self.dimension()
+                .coords()
+                .filter(|coord| self.est_menacee(owner, *coord))
+                .collect()
+        }
+
+        /// Champs de distances du plateau; `Plateau` les garde jusqu'à la prochaine pose.
+        fn distances(&self) -> Rc<Distances> {
+            Rc::new(Distances::calculer(self))
+        }
+
+        /// Cases et protéines que chaque joueur atteint en premier, gardées comme `distances`.
+        fn territoire(&self) -> Rc<Territoire> {
+            Rc::new(Territoire::calculer(self))
+        }
+    }
+
+    // Valeur dérivée gardée jusqu'à la prochaine modification: ignorée par `==` et `Debug`.
+    #[derive(Clone)]
+    struct Cache<T>(OnceCell<T>);
+
+    impl<T> Default for Cache<T> {
+        fn default() -> Self {
+            Self(OnceCell::new())
+        }
+    }
+
+    impl<T> PartialEq for Cache<T> {
+        fn eq(&self, _: &Self) -> bool {
+            true
+        }
+    }
+
+    impl<T> Eq for Cache<T> {}
+
+    impl<T> Debug for Cache<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("Cache")
+        }
+    }
+
+    // splitmix64: une clé pseudo-aléatoire fixe par caractéristique, sans table à initialiser.
+    fn melanger(graine: u64) -> u64 {
+        let mut z = graine.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn zobrist_case(cell: Cell) -> u64 {
+        let code = match cell.entity {
+            Entity::Void => return 0,
+            Entity::Wall => 1,
+            Entity::Protein(protein) => 2 + protein as u64,
+            Entity::Organe(org) => {
+                6 + org.owner as u64 * 20 + org.organe_type as u64 * 4 + org.dir as u64
+            }
+        };
+        melanger(u64::from(cell.coord.x) << 24 | u64::from(cell.coord.y) << 16 | code)
+    }
+
+    fn zobrist_ressources(ressources: OwnerMap<Ressource>) -> u64 {
+        iproduct!([Owner::Me, Owner::Ennemy], Protein::all())
+            .map(|(owner, protein)| {
+                let quantite = ressources.get(owner).get(&protein);
+                melanger(
+                    1 << 48 | (owner as u64) << 40 | (protein as u64) << 32 | u64::from(quantite),
+                )
+            })
+            .fold(0, |empreinte, cle| empreinte ^ cle)
+    }
+
+    // Plusieurs harvesters d'un joueur sur la même case ne récoltent qu'une fois: on garde
+    // celui de plus petit id pour que le résultat ne dépende pas de l'ordre de parcours.
+    fn recolter(recoltes: &mut CoordMap<Harvesting>, cible: Coord, harvesting: Harvesting) {
+        recoltes
+            .entry(cible)
+            .and_modify(|garde| {
+                if harvesting.harvester_id.get_num() < garde.harvester_id.get_num() {
+                    *garde = harvesting;
+                }
+            })
+            .or_insert(harvesting);
     }
 
     #[derive(Debug, Clone, PartialEq, Eq)]
@@ -358,10 +2283,11 @@ pub mod state {
         id_map: IdMap<OrgWithCoord>,
         empty_cells: CoordMap<EmptyCell>,
         prot_cells: CoordMap<Protein>,
-        harvesting_cells: CoordMap<Harvesting>,
+        harvesting_cells: OwnerMap<CoordMap<Harvesting>>,
 
         organes_by_root: IdMap<HashSet<OrgWithCoord>>,
         child_map: IdMap<HashSet<Id>>,
+        empreinte: u64,
     }
 
     impl InitState {
@@ -376,19 +2302,19 @@ pub mod state {
                 OwnerMap::new(ressources_ami, ressources_ennemy);
             let mut max_id: OwnerMap<Id> = OwnerMap::default();
             let mut root_set: OwnerMap<HashSet<Id>> = OwnerMap::default();
-            let coord_cells: CoordMap<Cell> = cells.iter().map(|c| (c.coord, c.clone())).collect();
+            let coord_cells: CoordMap<Cell> = cells.iter().map(|c| (c.coord, *c)).collect();
             let mut id_map: IdMap<OrgWithCoord> = HashMap::new();
             let mut prot_cells: CoordMap<Protein> = HashMap::new();
             let mut empty_cells: CoordMap<EmptyCell> =
                 iproduct!(0..dimension.width, 0..dimension.height)
                     .map(|(x, y)| (Coord { x, y }, EmptyCell))
                     .collect();
-            let mut harvesting_cells: CoordMap<Harvesting> = HashMap::new();
+            let mut harvesting_cells: OwnerMap<CoordMap<Harvesting>> = OwnerMap::default();
             let mut organes_by_root: IdMap<HashSet<OrgWithCoord>> = HashMap::new();
             let mut child_map: IdMap<HashSet<Id>> = HashMap::new();
 
             for cell in cells.into_iter() {
-                let Cell { coord, entity } = cell.clone();
+                let Cell { coord, entity } = cell;
                 empty_cells.remove(&coord);
                 match entity {
                     Entity::Void => {}
@@ -411,7 +2337,7 @@ pub mod state {
                             .entry(org.root_id)
                             .or_default()
                             .insert(org_with_coord);
-                        if org.id != org.parent_id {
+                        if org.id != org.parent_id && org.organe_type != OrganeType::Root {
                             child_map.entry(org.parent_id).or_default().insert(org.id);
                         };
 
@@ -430,10 +2356,11 @@ pub mod state {
                                 entity: Entity::Protein(prot),
                             }) = en_face
                             {
-                                harvesting_cells.insert(
-                                    coord_prot.clone(),
+                                recolter(
+                                    harvesting_cells.get_mut(org.owner),
+                                    *coord_prot,
                                     Harvesting {
-                                        protein: prot.clone(),
+                                        protein: *prot,
                                         direction: org.dir,
                                         harvester_coord: coord,
                                         harvester_id: org.id,
@@ -444,6 +2371,12 @@ pub mod state {
                     }
                 };
             }
+            let empreinte = dimension
+                .coords()
+                .filter_map(|coord| coord_cells.get(&coord))
+                .fold(zobrist_ressources(ressources_map), |empreinte, cell| {
+                    empreinte ^ zobrist_case(*cell)
+                });
             Self {
                 dimension,
                 ressources_map,
@@ -458,6 +2391,7 @@ pub mod state {
                 harvesting_cells,
                 child_map,
                 organes_by_root,
+                empreinte,
             }
         }
 
@@ -468,7 +2402,7 @@ pub mod state {
 
     impl Display for InitState {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            Debug::fmt(&self, f)
+            Display::fmt(&Rendu(self), f)
         }
     }
 
@@ -483,20 +2417,21 @@ pub mod state {
         fn ressource(&self) -> OwnerMap<Ressource> {
             self.ressources_map
         }
+        fn zobrist(&self) -> u64 {
+            self.empreinte
+        }
+
         fn get_by_coord(&self, coord: Coord) -> Option<Cell> {
             match self.coord_cells.get(&coord) {
-                Some(cell) => Some(cell.clone()),
-                None => match self.empty_cells.get(&coord) {
-                    Some(_) => Some(Cell {
-                        coord,
-                        entity: Entity::Void,
-                    }),
-                    None => None,
-                },
+                Some(cell) => Some(*cell),
+                None => self.empty_cells.get(&coord).map(|_| Cell {
+                    coord,
+                    entity: Entity::Void,
+                }),
             }
         }
 
-        fn harvesting(&self) -> CoordMap<Harvesting> {
+        fn harvesting(&self) -> OwnerMap<CoordMap<Harvesting>> {
             self.harvesting_cells.clone()
         }
 
@@ -524,6 +2459,10 @@ pub mod state {
             self.id_map.get(&id).copied()
         }
 
+        fn dimension(&self) -> Dimension {
+            self.dimension
+        }
+
         fn roots(&self) -> OwnerMap<HashSet<Id>> {
             self.root_set.clone()
         }
@@ -538,528 +2477,619 @@ pub mod state {
         }
     }
 
-    #[derive(Debug)]
-    pub struct WaitStep {
-        previous: Rc<dyn State>,
-        decision: Wait,
-    }
-
-    impl WaitStep {
-        pub fn new(previous: Rc<dyn State>, decision: Wait) -> Self {
-            Self { previous, decision }
-        }
-    }
-
-    impl HaveRoot for WaitStep {
-        fn get_root_id(&self) -> Id {
-            self.decision.root_id
-        }
+    /// Plateau à plat: les cases sont rangées par `y * width + x` et la table des organes est
+    /// indexée par numéro d'id. Un clone ne coûte que quelques copies de `Vec` et les coups se
+    /// jouent sur place, contrairement à une chaîne d'étapes qu'il faut remonter à chaque requête.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Plateau {
+        dimension: Dimension,
+        cases: Vec<Entity>,
+        organes: OwnerMap<Vec<Option<Coord>>>,
+        ressources: OwnerMap<Ressource>,
+        max_id: OwnerMap<Id>,
+        a_jouer: OwnerMap<Vec<Id>>,
+        attaques: Vec<(Coord, Attacking)>,
+        planification: Planification,
+        // Partie cases de l'empreinte de Zobrist, tenue à jour à chaque pose.
+        empreinte: u64,
+        distances: Cache<Rc<Distances>>,
+        territoire: Cache<Rc<Territoire>>,
+        revenu_sacrifie: OwnerMap<Ressource>,
     }
 
-    impl State for WaitStep {
-        fn planification(&self) -> Planification {
-            self.previous.planification().add_decision(Decision::Wait)
-        }
-
-        fn max_id(&self) -> OwnerMap<Id> {
-            self.previous.max_id()
-        }
-
-        fn ressource(&self) -> OwnerMap<Ressource> {
-            self.previous.ressource()
+    impl Plateau {
+        pub fn new(state: &dyn State) -> Self {
+            let dimension = state.dimension();
+            let cases: Vec<Entity> = dimension
+                .coords()
+                .map(|coord| state.get_by_coord(coord).map_or(Entity::Void, |c| c.entity))
+                .collect();
+            let empreinte =
+                dimension
+                    .coords()
+                    .zip(cases.iter())
+                    .fold(0, |empreinte, (coord, entity)| {
+                        empreinte
+                            ^ zobrist_case(Cell {
+                                coord,
+                                entity: *entity,
+                            })
+                    });
+            let a_jouer = |owner: Owner| {
+                let mut ids: Vec<Id> = state.action_set().get(owner).iter().copied().collect();
+                ids.sort_by_key(|id| id.get_num());
+                ids
+            };
+            let mut plateau = Self {
+                dimension,
+                cases,
+                organes: OwnerMap::default(),
+                ressources: state.ressource(),
+                max_id: state.max_id(),
+                a_jouer: OwnerMap::new(a_jouer(Owner::Me), a_jouer(Owner::Ennemy)),
+                attaques: state.attacking().into_iter().collect(),
+                planification: state.planification(),
+                empreinte,
+                distances: Cache::default(),
+                territoire: Cache::default(),
+                revenu_sacrifie: OwnerMap::default(),
+            };
+            for coord in dimension.coords() {
+                if let Some(Entity::Organe(org)) = plateau.case(coord) {
+                    plateau.referencer(org.id, Some(coord));
+                }
+            }
+            plateau
         }
 
-        fn get_by_coord(&self, coord: Coord) -> Option<Cell> {
-            self.previous.get_by_coord(coord)
+        /// Les racines de `owner` qui n'ont pas encore joué ce tour, par id croissant.
+        pub fn a_jouer(&self, owner: Owner) -> &[Id] {
+            self.a_jouer.get(owner)
         }
 
-        fn harvesting(&self) -> CoordMap<Harvesting> {
-            self.previous.harvesting()
+        /// Fait pousser un organe; renvoie `false` sans rien modifier si le coup est impossible.
+        pub fn grow(&mut self, grow: Grow) -> bool {
+            let owner = grow.parent_id.get_owner();
+            let Some((pousse, reste)) = self.legale(owner, Decision::Grow(grow)) else {
+                return false;
+            };
+            let id = self.nouvel_id(owner, reste, self.fertile(grow.coord).flatten());
+            self.poser(
+                grow.coord,
+                Entity::Organe(Organe {
+                    id,
+                    parent_id: grow.parent_id,
+                    root_id: pousse.root_id,
+                    organe_type: grow.organe_type,
+                    dir: grow.direction,
+                    owner,
+                }),
+            );
+            if grow.organe_type == OrganeType::Tentacle {
+                if let Some(Cell {
+                    coord,
+                    entity: Entity::Organe(cible),
+                }) = self.en_face(grow.coord, grow.direction)
+                {
+                    if owner.is_ennemy(cible.owner) {
+                        let attaque = Attacking {
+                            target_coord: coord,
+                            target_id: cible.id,
+                        };
+                        self.attaques.push((grow.coord, attaque));
+                    }
+                }
+            }
+            self.jouer(pousse.root_id, Decision::Grow(grow));
+            true
         }
 
-        fn empty_cell(&self) -> CoordMap<EmptyCell> {
-            self.previous.empty_cell()
+        /// Lance une spore qui devient une nouvelle racine; renvoie `false` si c'est impossible.
+        pub fn spore(&mut self, spore: Spore) -> bool {
+            let owner = spore.parent_id.get_owner();
+            let Some((pousse, reste)) = self.legale(owner, Decision::Spore(spore)) else {
+                return false;
+            };
+            let id = self.nouvel_id(owner, reste, self.fertile(spore.coord).flatten());
+            self.poser(
+                spore.coord,
+                Entity::Organe(Organe {
+                    id,
+                    parent_id: Id::zero(owner),
+                    root_id: id,
+                    organe_type: OrganeType::Root,
+                    dir: Direction::N,
+                    owner,
+                }),
+            );
+            self.jouer(pousse.root_id, Decision::Spore(spore));
+            true
         }
 
-        fn protein(&self) -> CoordMap<Protein> {
-            self.previous.protein()
+        pub fn wait(&mut self, root_id: Id) {
+            self.jouer(root_id, Decision::Wait);
         }
 
-        fn attacking(&self) -> CoordMap<Attacking> {
-            self.previous.attacking()
-        }
+        /// Joue les décisions des deux joueurs en même temps puis finit le tour, comme l'arbitre.
+        pub fn resoudre(&mut self, decisions: OwnerMap<Vec<Decision>>) {
+            let mut pousses: Vec<(Coord, Organe)> = Vec::new();
+            for owner in [Owner::Me, Owner::Ennemy] {
+                let racines = self.a_jouer(owner).to_vec();
+                let mut joues: HashMap<Id, Decision> = HashMap::new();
+                for decision in decisions.get(owner) {
+                    let Some((pousse, reste)) = self.legale(owner, *decision) else {
+                        continue;
+                    };
+                    if joues.contains_key(&pousse.root_id) {
+                        continue;
+                    }
+                    *self.ressources.get_mut(owner) = reste;
+                    self.max_id = self.max_id.suivant(owner);
+                    let id = *self.max_id.get(owner);
+                    let (parent_id, root_id) = match pousse.parent_id {
+                        Some(parent_id) => (parent_id, pousse.root_id),
+                        None => (Id::zero(owner), id),
+                    };
+                    pousses.push((
+                        pousse.coord,
+                        Organe {
+                            id,
+                            parent_id,
+                            root_id,
+                            organe_type: pousse.organe_type,
+                            dir: pousse.dir,
+                            owner,
+                        },
+                    ));
+                    joues.insert(pousse.root_id, *decision);
+                }
+                // Chaque décision est rangée à l'indice de sa racine, WAIT compris.
+                for root_id in racines {
+                    let decision = joues.get(&root_id).copied().unwrap_or(Decision::Wait);
+                    self.jouer(root_id, decision);
+                }
+            }
 
-        fn child_by_parent(&self, parent_id: Id) -> HashSet<Id> {
-            self.previous.child_by_parent(parent_id)
-        }
+            for (coord, organe) in pousses.iter() {
+                if pousses.iter().filter(|(c, _)| c == coord).count() > 1 {
+                    self.poser(*coord, Entity::Wall);
+                    continue;
+                }
+                if let Some(Entity::Protein(protein)) = self.case(*coord) {
+                    *self.ressources.get_mut(organe.owner) =
+                        self.ressources.get(organe.owner).ajout_3(protein);
+                }
+                self.poser(*coord, Entity::Organe(*organe));
+            }
 
-        fn action_set(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous
-                .action_set()
-                .update(self.get_owner(), |mut ids| {
-                    ids.remove(&self.get_root_id());
-                    ids
+            let attaques: Vec<(Coord, Attacking)> = [Owner::Me, Owner::Ennemy]
+                .into_iter()
+                .flat_map(|owner| self.organes_de(owner))
+                .filter(|org| org.organe_type == OrganeType::Tentacle)
+                .filter_map(|org| match self.en_face(org.coord, org.dir)? {
+                    Cell {
+                        coord,
+                        entity: Entity::Organe(cible),
+                    } if cible.owner.is_ennemy(org.id.get_owner()) => Some((
+                        org.coord,
+                        Attacking {
+                            target_coord: coord,
+                            target_id: cible.id,
+                        },
+                    )),
+                    _ => None,
                 })
-        }
-
-        fn get_by_id(&self, id: Id) -> Option<OrgWithCoord> {
-            self.previous.get_by_id(id)
-        }
-
-        fn roots(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous.roots()
-        }
-
-        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord> {
-            self.previous.organes_by_root(root_id)
-        }
-    }
-
-    #[derive(Debug)]
-    pub struct GrowStep {
-        previous: Rc<dyn State>,
-        decision: Grow,
-        ressource_map: OwnerMap<Ressource>,
-    }
-
-    impl GrowStep {
-        pub fn try_new(previous: Rc<dyn State>, decision: Grow) -> Option<Self> {
-            let mut new_ressource = previous
-                .ressource()
-                .get(decision.parent_id.get_owner())
-                .checked_sub(decision.organe_type.prix())?;
-            let fertilite = previous.fertile_cell().get(&decision.coord).cloned()?;
-            if let Some(prot) = fertilite.protein {
-                new_ressource = new_ressource.ajout_3(prot.clone());
-            };
-            let ressource_map = previous
-                .ressource()
-                .update(decision.parent_id.get_owner(), |_| new_ressource);
-            Some(Self {
-                previous,
-                decision,
-                ressource_map,
-            })
-        }
+                .collect();
+            self.attaques.extend(attaques);
+            self.finir_tour();
+        }
+
+        /// Détruit les organes attaqués et leurs descendants, récolte, puis rend la main.
+        pub fn finir_tour(&mut self) {
+            let mut a_detruire: Vec<Id> =
+                self.attaques.drain(..).map(|(_, a)| a.target_id).collect();
+            let mut detruits: HashSet<Id> = HashSet::new();
+            while let Some(id) = a_detruire.pop() {
+                if detruits.insert(id) {
+                    a_detruire.extend(self.child_by_parent(id));
+                }
+            }
+            for id in detruits {
+                if let Some(org) = self.get_by_id(id) {
+                    self.changer_case(org.coord, Entity::Void);
+                    self.referencer(id, None);
+                }
+            }
 
-        fn get_last_id(&self) -> Id {
-            self.max_id().get(self.get_owner()).clone()
-        }
-    }
+            let recoltes = self.harvesting();
+            for owner in [Owner::Me, Owner::Ennemy] {
+                for harvesting in recoltes.get(owner).values() {
+                    *self.ressources.get_mut(owner) =
+                        self.ressources.get(owner).ajout_1(harvesting.protein);
+                }
+            }
 
-    impl HaveRoot for GrowStep {
-        fn get_root_id(&self) -> Id {
-            self.previous
-                .get_by_id(self.decision.parent_id)
-                .map(|org| org.root_id)
-                .expect("pas de parent existant")
+            for owner in [Owner::Me, Owner::Ennemy] {
+                let racines: Vec<Id> = self
+                    .organes_de(owner)
+                    .filter(|org| org.organe_type == OrganeType::Root)
+                    .map(|org| org.id)
+                    .collect();
+                *self.a_jouer.get_mut(owner) = racines;
+            }
+            self.planification = std::mem::take(&mut self.planification).new_turn();
         }
-    }
 
-    impl State for GrowStep {
-        fn planification(&self) -> Planification {
-            self.previous
-                .planification()
-                .add_decision(Decision::Grow(self.decision))
+        // La pousse et le stock restant si `owner` peut jouer `decision`: case voisine du parent
+        // ou sur la ligne d'un sporer, racine qui n'a pas encore joué, prix payable.
+        fn legale(&self, owner: Owner, decision: Decision) -> Option<(Pousse, Ressource)> {
+            let pousse = resoudre_decision(self, owner, decision)?;
+            if !self.a_jouer(owner).contains(&pousse.root_id) {
+                return None;
+            }
+            let reste = self.ressources.get(owner).checked_sub(pousse.prix)?;
+            Some((pousse, reste))
         }
 
-        fn max_id(&self) -> OwnerMap<Id> {
-            self.previous.max_id().increment(self.get_owner())
+        fn index(&self, coord: Coord) -> usize {
+            usize::from(coord.y) * usize::from(self.dimension.width) + usize::from(coord.x)
         }
 
-        fn ressource(&self) -> OwnerMap<Ressource> {
-            self.ressource_map
+        fn case(&self, coord: Coord) -> Option<Entity> {
+            self.dimension
+                .contient(coord)
+                .then(|| self.cases[self.index(coord)])
         }
 
-        fn get_by_coord(&self, coord: Coord) -> Option<Cell> {
-            if self.decision.coord == coord {
-                Some(Cell {
-                    coord: self.decision.coord,
-                    entity: Entity::Organe(Organe {
-                        id: self.get_last_id(),
-                        parent_id: self.decision.parent_id,
-                        root_id: self.get_root_id(),
-                        organe_type: self.decision.organe_type,
-                        dir: self.decision.direction,
-                        owner: self.get_owner(),
-                    }),
-                })
-            } else {
-                self.previous.get_by_coord(coord)
+        // `Some(protéine éventuelle)` si l'on peut pousser sur la case.
+        fn fertile(&self, coord: Coord) -> Option<Option<Protein>> {
+            match self.case(coord)? {
+                Entity::Void => Some(None),
+                Entity::Protein(protein) => Some(Some(protein)),
+                _ => None,
             }
         }
 
-        fn get_by_id(&self, id: Id) -> Option<OrgWithCoord> {
-            if self.get_last_id() == id {
-                Some(OrgWithCoord {
-                    coord: self.decision.coord,
-                    id,
-                    parent_id: self.decision.parent_id,
-                    root_id: self.get_root_id(),
-                    organe_type: self.decision.organe_type,
-                    dir: self.decision.direction,
-                })
-            } else {
-                self.previous.get_by_id(id)
-            }
+        // Comme `fertile`, mais refuse aussi les cases en face d'un tentacle adverse de `owner`.
+        fn fertile_pour(&self, owner: Owner, coord: Coord) -> Option<Option<Protein>> {
+            self.fertile(coord)
+                .filter(|_| !self.est_menacee(owner, coord))
         }
 
-        fn harvesting(&self) -> CoordMap<Harvesting> {
-            let mut retour = self.previous.harvesting();
-            retour.remove(&self.decision.coord);
-            if self.decision.organe_type != OrganeType::Harvester {
-                return retour;
-            }
-            let en_face = self.en_face(self.decision.coord, self.decision.direction);
-
-            if let Some(Cell {
-                coord: coord_prot,
-                entity: Entity::Protein(prot),
-            }) = en_face
-            {
-                retour.insert(
-                    coord_prot.clone(),
-                    Harvesting {
-                        protein: prot.clone(),
-                        direction: self.decision.direction,
-                        harvester_coord: self.decision.coord,
-                        harvester_id: self.get_last_id(),
-                    },
-                );
+        fn nouvel_id(&mut self, owner: Owner, reste: Ressource, protein: Option<Protein>) -> Id {
+            *self.ressources.get_mut(owner) = match protein {
+                Some(protein) => reste.ajout_3(protein),
+                None => reste,
             };
-            retour
+            self.max_id = self.max_id.suivant(owner);
+            *self.max_id.get(owner)
         }
 
-        fn empty_cell(&self) -> CoordMap<EmptyCell> {
-            let mut retour = self.previous.empty_cell();
-            retour.remove(&self.decision.coord);
-            retour
-        }
-
-        fn protein(&self) -> CoordMap<Protein> {
-            let mut retour = self.previous.protein();
-            retour.remove(&self.decision.coord);
-            retour
+        fn poser(&mut self, coord: Coord, entity: Entity) {
+            let perdu = self.revenu_perdu(coord);
+            for owner in [Owner::Me, Owner::Ennemy] {
+                *self.revenu_sacrifie.get_mut(owner) =
+                    *self.revenu_sacrifie.get(owner) + *perdu.get(owner);
+            }
+            self.changer_case(coord, entity);
+            if let Entity::Organe(organe) = entity {
+                self.referencer(organe.id, Some(coord));
+            }
         }
 
-        fn attacking(&self) -> CoordMap<Attacking> {
-            let mut previous_attacking = self.previous.attacking();
-            if self.decision.organe_type == OrganeType::Tentacle {
-                let Some(en_face) = self.en_face(self.decision.coord, self.decision.direction)
-                else {
-                    return previous_attacking;
-                };
-                let Entity::Organe(org) = en_face.entity else {
-                    return previous_attacking;
-                };
-                if self.get_owner().is_ennemy(org.owner) {
-                    previous_attacking.insert(
-                        self.decision.coord,
-                        Attacking {
-                            target_coord: en_face.coord,
-                            target_id: org.id,
-                        },
-                    );
-                };
+        fn changer_case(&mut self, coord: Coord, entity: Entity) {
+            let index = self.index(coord);
+            let avant = Cell {
+                coord,
+                entity: self.cases[index],
             };
-            previous_attacking
+            self.empreinte ^= zobrist_case(avant) ^ zobrist_case(Cell { coord, entity });
+            self.cases[index] = entity;
+            self.distances = Cache::default();
+            self.territoire = Cache::default();
         }
 
-        fn child_by_parent(&self, parent_id: Id) -> HashSet<Id> {
-            let mut retour = self.previous.child_by_parent(parent_id);
-            if parent_id == self.decision.parent_id {
-            retour.insert(self.get_last_id());
+        fn referencer(&mut self, id: Id, coord: Option<Coord>) {
+            let table = self.organes.get_mut(id.get_owner());
+            let num = usize::from(id.get_num());
+            if table.len() <= num {
+                table.resize(num + 1, None);
             }
-            retour
-        }
-
-        fn action_set(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous
-                .action_set()
-                .update(self.decision.parent_id.get_owner(), |mut ids| {
-                    ids.remove(&self.get_root_id());
-                    ids
-                })
+            table[num] = coord;
         }
 
-        fn roots(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous.roots()
+        fn jouer(&mut self, root_id: Id, decision: Decision) {
+            let owner = root_id.get_owner();
+            self.a_jouer.get_mut(owner).retain(|id| *id != root_id);
+            self.planification =
+                std::mem::take(&mut self.planification).add_decision_for(owner, decision);
         }
 
-        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord> {
-            let mut retour = self.previous.organes_by_root(root_id);
-            if self.get_root_id() == root_id {
-                if let Some(org) = self.get_by_id(self.get_last_id()) {
-                    retour.insert(org);
-                };
-            };
-            retour
+        fn organes_de(&self, owner: Owner) -> impl Iterator<Item = OrgWithCoord> + '_ {
+            self.organes
+                .get(owner)
+                .iter()
+                .flatten()
+                .filter_map(|coord| match self.case(*coord)? {
+                    Entity::Organe(org) => Some(OrgWithCoord {
+                        coord: *coord,
+                        id: org.id,
+                        parent_id: org.parent_id,
+                        root_id: org.root_id,
+                        organe_type: org.organe_type,
+                        dir: org.dir,
+                    }),
+                    _ => None,
+                })
         }
     }
 
-    #[derive(Debug)]
-    pub struct SporeStep {
-        previous: Rc<dyn State>,
-        decision: Spore,
-        ressource_map: OwnerMap<Ressource>,
-    }
-
-    impl SporeStep {
-        pub fn try_new(previous: Rc<dyn State>, decision: Spore) -> Option<Self> {
-            let mut new_ressource = previous
-                .ressource()
-                .get(decision.parent_id.get_owner())
-                .checked_sub(Ressource::new(1, 1, 1, 1))?;
-            let fertilite = previous.fertile_cell().get(&decision.coord).cloned()?;
-            if let Some(prot) = fertilite.protein {
-                new_ressource = new_ressource.ajout_3(prot.clone());
-            };
-            let ressource_map = previous
-                .ressource()
-                .update(decision.parent_id.get_owner(), |_| new_ressource);
-
-            Some(Self {
-                decision,
-                previous,
-                ressource_map,
-            })
-        }
-
-        fn get_new_root_id(&self) -> Id {
-            self.max_id().get(self.get_owner()).clone()
+    impl Display for Plateau {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            Display::fmt(&Rendu(self), f)
         }
     }
 
-    impl HaveRoot for SporeStep {
-        fn get_root_id(&self) -> Id {
-            self.previous
-                .get_by_id(self.decision.parent_id)
-                .map(|org| org.root_id)
-                .expect("pas de parent existant pour SporeStep")
+    impl State for Plateau {
+        fn planification(&self) -> Planification {
+            self.planification.clone()
         }
-    }
 
-    impl State for SporeStep {
-        fn planification(&self) -> Planification {
-            self.previous
-                .planification()
-                .add_decision(Decision::Spore(self.decision))
+        fn action_set(&self) -> OwnerMap<HashSet<Id>> {
+            OwnerMap::new(
+                self.a_jouer(Owner::Me).iter().copied().collect(),
+                self.a_jouer(Owner::Ennemy).iter().copied().collect(),
+            )
         }
 
         fn max_id(&self) -> OwnerMap<Id> {
-            self.previous.max_id().increment(self.get_owner())
+            self.max_id
+        }
+
+        fn dimension(&self) -> Dimension {
+            self.dimension
         }
 
         fn ressource(&self) -> OwnerMap<Ressource> {
-            self.ressource_map
+            self.ressources
         }
 
         fn get_by_coord(&self, coord: Coord) -> Option<Cell> {
-            if coord == self.decision.coord {
-                Some(Cell {
-                    coord,
-                    entity: Entity::Organe(Organe {
-                        id: self.get_new_root_id(),
-                        parent_id: Id::zero(self.get_owner()),
-                        root_id: self.get_new_root_id(),
-                        organe_type: OrganeType::Root,
-                        dir: Direction::N,
-                        owner: self.get_owner(),
-                    }),
-                })
-            } else {
-                self.previous.get_by_coord(coord)
-            }
+            self.case(coord).map(|entity| Cell { coord, entity })
         }
 
         fn get_by_id(&self, id: Id) -> Option<OrgWithCoord> {
-            if self.get_new_root_id() == id {
-                Some(OrgWithCoord {
-                    coord: self.decision.coord,
+            let coord = (*self
+                .organes
+                .get(id.get_owner())
+                .get(usize::from(id.get_num()))?)?;
+            match self.case(coord)? {
+                Entity::Organe(org) if org.id == id => Some(OrgWithCoord {
+                    coord,
                     id,
-                    parent_id: Id::zero(self.get_owner()),
-                    root_id: id,
-                    organe_type: OrganeType::Root,
-                    dir: Direction::N,
-                })
-            } else {
-                self.previous.get_by_id(id)
+                    parent_id: org.parent_id,
+                    root_id: org.root_id,
+                    organe_type: org.organe_type,
+                    dir: org.dir,
+                }),
+                _ => None,
             }
         }
 
-        fn attacking(&self) -> CoordMap<Attacking> {
-            self.previous.attacking()
-        }
-
-        fn harvesting(&self) -> CoordMap<Harvesting> {
-            self.previous.harvesting()
-        }
-
-        fn empty_cell(&self) -> CoordMap<EmptyCell> {
-            let mut retour = self.previous.empty_cell();
-            retour.remove(&self.decision.coord);
-            retour
-        }
-
-        fn protein(&self) -> CoordMap<Protein> {
-            let mut retour = self.previous.protein();
-            retour.remove(&self.decision.coord);
-            retour
+        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord> {
+            self.organes_de(root_id.get_owner())
+                .filter(|org| org.root_id == root_id)
+                .collect()
         }
 
         fn child_by_parent(&self, parent_id: Id) -> HashSet<Id> {
-            self.previous.child_by_parent(parent_id)
-        }
-
-        fn action_set(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous.action_set().update(Owner::Me, |mut ids| {
-                ids.remove(&self.get_root_id());
-                ids
-            })
+            self.organes_de(parent_id.get_owner())
+                .filter(|org| org.parent_id == parent_id && org.id != parent_id)
+                .filter(|org| org.organe_type != OrganeType::Root)
+                .map(|org| org.id)
+                .collect()
         }
 
         fn roots(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous
-                .roots()
-                .update(self.decision.parent_id.get_owner(), |mut ids| {
-                    ids.insert(self.get_new_root_id());
-                    ids
-                })
-        }
-
-        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord> {
-            let mut retour = self.previous.organes_by_root(root_id);
-            if self.get_new_root_id() == root_id {
-                if let Some(org) = self.get_by_id(self.get_new_root_id()) {
-                    retour.insert(org);
-                };
+            let racines = |owner| {
+                self.organes_de(owner)
+                    .filter(|org| org.organe_type == OrganeType::Root)
+                    .map(|org| org.id)
+                    .collect()
             };
-            retour
+            OwnerMap::new(racines(Owner::Me), racines(Owner::Ennemy))
         }
-    }
-
-    #[derive(Debug)]
-    pub struct EndTurn {
-        previous: Rc<dyn State>,
-        detruit_coord: HashSet<Coord>,
-        detruit_id: HashSet<Id>,
-    }
 
-    fn get_childs_recursive(state: Rc<dyn State>, parent_id: Id) -> (HashSet<Id>, HashSet<Coord>) {
-        let mut coords: HashSet<Coord> = HashSet::new();
-        let mut ids: HashSet<Id> = HashSet::new();
-        for child in state.child_by_parent(parent_id) {
-            let (next_ids, next_coords) = get_childs_recursive(state.clone(), child);
-            ids.insert(child);
-            if let Some(org) = state.get_by_id(child) {
-                coords.insert(org.coord);
-            }
-            ids = ids.union(&next_ids).cloned().collect();
-            coords = coords.union(&next_coords).cloned().collect();
+        fn attacking(&self) -> CoordMap<Attacking> {
+            self.attaques.iter().copied().collect()
         }
-        (ids, coords)
-    }
 
-    impl EndTurn {
-        pub fn new(state: Rc<dyn State>) -> Self {
-            let mut detruit_coord: HashSet<Coord> = HashSet::new();
-            let mut detruit_id: HashSet<Id> = HashSet::new();
-            for attacked in state.attacking().into_values() {
-                let (ids, coords) = get_childs_recursive(state.clone(), attacked.target_id);
-                detruit_coord = detruit_coord.union(&coords).cloned().collect();
-                detruit_id = detruit_id.union(&ids).cloned().collect();
-            }
-            Self {
-                previous: state,
-                detruit_coord,
-                detruit_id,
+        fn harvesting(&self) -> OwnerMap<CoordMap<Harvesting>> {
+            let mut recoltes: OwnerMap<CoordMap<Harvesting>> = OwnerMap::default();
+            for owner in [Owner::Me, Owner::Ennemy] {
+                for org in self
+                    .organes_de(owner)
+                    .filter(|org| org.organe_type == OrganeType::Harvester)
+                {
+                    if let Some(Cell {
+                        coord,
+                        entity: Entity::Protein(protein),
+                    }) = self.en_face(org.coord, org.dir)
+                    {
+                        let harvesting = Harvesting {
+                            protein,
+                            direction: org.dir,
+                            harvester_coord: org.coord,
+                            harvester_id: org.id,
+                        };
+                        recolter(recoltes.get_mut(owner), coord, harvesting);
+                    }
+                }
             }
+            recoltes
         }
-    }
 
-    impl State for EndTurn {
-        fn planification(&self) -> Planification {
-            self.previous.planification().new_turn()
+        fn empty_cell(&self) -> CoordMap<EmptyCell> {
+            self.dimension
+                .coords()
+                .zip(self.cases.iter())
+                .filter(|(_, entity)| **entity == Entity::Void)
+                .map(|(coord, _)| (coord, EmptyCell))
+                .collect()
         }
 
-        fn get_by_coord(&self, coord: Coord) -> Option<Cell> {
-            if self.detruit_coord.contains(&coord) {
-                Some(Cell {
-                    coord,
-                    entity: Entity::Void,
+        fn protein(&self) -> CoordMap<Protein> {
+            self.dimension
+                .coords()
+                .zip(self.cases.iter())
+                .filter_map(|(coord, entity)| match entity {
+                    Entity::Protein(protein) => Some((coord, *protein)),
+                    _ => None,
                 })
-            } else {
-                self.previous.get_by_coord(coord)
-            }
-        }
-
-        fn get_by_id(&self, id: Id) -> Option<OrgWithCoord> {
-            if self.detruit_id.contains(&id) {
-                None
-            } else {
-                self.previous.get_by_id(id)
-            }
-        }
-
-        fn max_id(&self) -> OwnerMap<Id> {
-            self.previous.max_id()
+                .collect()
         }
 
-        fn ressource(&self) -> OwnerMap<Ressource> {
-            self.previous.harvesting().into_iter().fold(
-                self.previous.ressource(),
-                |res, (_c, harvesting)| {
-                    res.update(harvesting.harvester_id.get_owner(), |r| {
-                        r.ajout_1(harvesting.protein)
-                    })
-                },
-            )
+        fn nb_organe(&self, owner: Owner) -> usize {
+            self.organes_de(owner).count()
         }
 
-        fn harvesting(&self) -> CoordMap<Harvesting> {
-            self.previous.harvesting()
+        fn fertile_cell(&self, owner: Owner) -> CoordMap<Fertile> {
+            let menacees = self.menaces(owner);
+            self.dimension
+                .coords()
+                .filter(|coord| !menacees.contains(coord))
+                .filter_map(|coord| {
+                    let protein = self.fertile(coord)?;
+                    Some((coord, Fertile { coord, protein }))
+                })
+                .collect()
         }
 
-        fn empty_cell(&self) -> CoordMap<EmptyCell> {
-            let mut empty_cells = self.previous.empty_cell();
-            empty_cells.extend(self.detruit_coord.iter().map(|c| (c.clone(), EmptyCell)));
-            empty_cells
+        fn zobrist(&self) -> u64 {
+            self.empreinte ^ zobrist_ressources(self.ressources)
         }
 
-        fn protein(&self) -> CoordMap<Protein> {
-            self.previous.protein()
+        fn revenu_sacrifie(&self) -> OwnerMap<Ressource> {
+            self.revenu_sacrifie
         }
 
-        fn attacking(&self) -> CoordMap<Attacking> {
-            CoordMap::new()
+        fn menaces(&self, owner: Owner) -> HashSet<Coord> {
+            self.organes_de(owner.switch_side())
+                .filter(|org| org.organe_type == OrganeType::Tentacle)
+                .filter_map(|org| org.coord.decaler(org.dir))
+                .filter(|coord| self.dimension.contient(*coord))
+                .collect()
         }
-
-        fn child_by_parent(&self, parent_id: Id) -> HashSet<Id> {
-            if self.detruit_id.contains(&parent_id) {
-                return HashSet::new();
-            }
-            self.previous.child_by_parent(parent_id).difference(&self.detruit_id).cloned().collect()
+
+        fn distances(&self) -> Rc<Distances> {
+            self.distances
+                .0
+                .get_or_init(|| Rc::new(Distances::calculer(self)))
+                .clone()
         }
 
-        fn action_set(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous.roots()
+        fn territoire(&self) -> Rc<Territoire> {
+            self.territoire
+                .0
+                .get_or_init(|| Rc::new(Territoire::calculer(self)))
+                .clone()
         }
 
-        fn roots(&self) -> OwnerMap<HashSet<Id>> {
-            self.previous.roots()
+        fn grow_candidate(&self, root_id: Id) -> CoordMap<GrowCandidate> {
+            self.organes_de(root_id.get_owner())
+                .filter(|org| org.root_id == root_id)
+                .flat_map(|org| {
+                    Direction::all()
+                        .into_iter()
+                        .filter_map(move |direction| org.coord.decaler(direction))
+                        .filter(|coord| self.fertile_pour(root_id.get_owner(), *coord).is_some())
+                        .map(move |coord| (coord, GrowCandidate { parent_id: org.id }))
+                })
+                .collect()
         }
+    }
 
-        fn organes_by_root(&self, root_id: Id) -> HashSet<OrgWithCoord> {
-            if self.detruit_id.contains(&root_id) {
-                HashSet::new()
-            } else {
-                self.previous.organes_by_root(root_id)
+    #[derive(Debug, Clone, Copy)]
+    struct Pousse {
+        root_id: Id,
+        parent_id: Option<Id>,
+        coord: Coord,
+        organe_type: OrganeType,
+        dir: Direction,
+        prix: Ressource,
+    }
+
+    fn resoudre_decision(state: &dyn State, owner: Owner, decision: Decision) -> Option<Pousse> {
+        match decision {
+            Decision::Wait => None,
+            Decision::Grow(grow) => {
+                let parent = state
+                    .get_by_id(grow.parent_id)
+                    .filter(|_| grow.parent_id.get_owner() == owner)?;
+                let voisin = Direction::all()
+                    .into_iter()
+                    .any(|dir| parent.coord.decaler(dir) == Some(grow.coord));
+                let fertile = matches!(
+                    state.get_by_coord(grow.coord).map(|c| c.entity),
+                    Some(Entity::Void | Entity::Protein(_))
+                ) && !state.est_menacee(owner, grow.coord);
+                if !voisin || grow.organe_type == OrganeType::Root || !fertile {
+                    return None;
+                }
+                Some(Pousse {
+                    root_id: parent.root_id,
+                    parent_id: Some(parent.id),
+                    coord: grow.coord,
+                    organe_type: grow.organe_type,
+                    dir: grow.direction,
+                    prix: grow.organe_type.prix(),
+                })
+            }
+            Decision::Spore(spore) => {
+                let parent = state
+                    .get_by_id(spore.parent_id)
+                    .filter(|_| spore.parent_id.get_owner() == owner)?;
+                let candidate = SporeCandidate {
+                    parent_id: spore.parent_id,
+                    coord: spore.coord,
+                };
+                if !state.spore_candidate(parent.root_id).contains(&candidate) {
+                    return None;
+                }
+                Some(Pousse {
+                    root_id: parent.root_id,
+                    parent_id: None,
+                    coord: spore.coord,
+                    organe_type: OrganeType::Root,
+                    dir: Direction::N,
+                    prix: Ressource::new(1, 1, 1, 1),
+                })
             }
         }
     }
+
+    /// Joue un tour complet des deux joueurs comme l'arbitre, voir `Plateau::resoudre`.
+    pub fn simuler_tour(state: &dyn State, decisions: OwnerMap<Vec<Decision>>) -> InitState {
+        let mut plateau = Plateau::new(state);
+        plateau.resoudre(decisions);
+        let dimension = plateau.dimension();
+        InitState::new(
+            dimension,
+            *plateau.ressource().get(Owner::Me),
+            *plateau.ressource().get(Owner::Ennemy),
+            ActionCount::new(u32::try_from(plateau.a_jouer(Owner::Me).len()).unwrap_or(u32::MAX)),
+            dimension
+                .coords()
+                .filter_map(|coord| plateau.get_by_coord(coord))
+                .filter(|cell| cell.entity != Entity::Void)
+                .collect(),
+        )
+    }
 }
 
 pub mod decision {
+    use std::str::{FromStr, SplitWhitespace};
+
     use super::atome::*;
 
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
@@ -1080,6 +3110,46 @@ pub mod decision {
         }
     }
 
+    impl Decision {
+        /// Relit une commande émise par un bot; les mots après la commande (message) sont ignorés.
+        pub fn from_command(commande: &str, owner: Owner) -> Option<Self> {
+            let mut mots = commande.split_whitespace();
+            fn nombre<T: FromStr>(mots: &mut SplitWhitespace) -> Option<T> {
+                mots.next()?.parse().ok()
+            }
+            match mots.next()? {
+                "WAIT" => Some(Decision::Wait),
+                "GROW" => {
+                    let parent_id = Id::new(owner, nombre(&mut mots)?);
+                    let coord = Coord {
+                        x: nombre(&mut mots)?,
+                        y: nombre(&mut mots)?,
+                    };
+                    let organe_type = OrganeType::from_command(mots.next()?)?;
+                    let direction = match mots.next() {
+                        Some(dir) => Direction::from_command(dir)?,
+                        None => Direction::N,
+                    };
+                    Some(Decision::Grow(Grow {
+                        parent_id,
+                        coord,
+                        organe_type,
+                        direction,
+                    }))
+                }
+                "SPORE" => {
+                    let parent_id = Id::new(owner, nombre(&mut mots)?);
+                    let coord = Coord {
+                        x: nombre(&mut mots)?,
+                        y: nombre(&mut mots)?,
+                    };
+                    Some(Decision::Spore(Spore { parent_id, coord }))
+                }
+                _ => None,
+            }
+        }
+    }
+
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
     pub struct Grow {
         pub parent_id: Id,
@@ -1159,6 +3229,13 @@ pub mod molecule {
                 Owner::Ennemy => &self.ennemy,
             }
         }
+
+        pub fn get_mut(&mut self, owner: Owner) -> &mut T {
+            match owner {
+                Owner::Me => &mut self.friend,
+                Owner::Ennemy => &mut self.ennemy,
+            }
+        }
     }
 
     impl OwnerMap<Id> {
@@ -1166,6 +3243,12 @@ pub mod molecule {
             self.update(owner, |id| id.increment())
         }
 
+        /// Prochain id de `owner`, pris sur le compteur commun aux deux joueurs.
+        pub fn suivant(self, owner: Owner) -> Self {
+            let max = self.friend.get_num().max(self.ennemy.get_num());
+            self.update(owner, |_| Id::new(owner, max).increment())
+        }
+
         pub fn insert_max(self, new_id: Id) -> Self {
             self.update(new_id.get_owner(), |id| {
                 if id.get_num() < new_id.get_num() {
@@ -1242,7 +3325,11 @@ pub mod molecule {
 }
 
 pub mod atome {
-    use std::{collections::HashMap, ops::Range};
+    use std::{
+        cmp::Ordering,
+        collections::HashMap,
+        ops::{Add, Mul, Range, Sub},
+    };
 
     use super::decision::Decision;
 
@@ -1250,20 +3337,14 @@ pub mod atome {
         fn to_command(&self) -> String;
     }
 
-    pub trait HaveRoot {
-        fn get_root_id(&self) -> Id;
+    pub trait FromCommand: Sized {
+        fn from_command(commande: &str) -> Option<Self>;
     }
 
     pub trait HaveOwner {
         fn get_owner(&self) -> Owner;
     }
 
-    impl<T: HaveRoot> HaveOwner for T {
-        fn get_owner(&self) -> Owner {
-            self.get_root_id().get_owner()
-        }
-    }
-
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
     pub enum Protein {
         A,
@@ -1278,6 +3359,30 @@ pub mod atome {
         }
     }
 
+    impl FromCommand for Protein {
+        fn from_command(commande: &str) -> Option<Self> {
+            match commande {
+                "A" => Some(Protein::A),
+                "B" => Some(Protein::B),
+                "C" => Some(Protein::C),
+                "D" => Some(Protein::D),
+                _ => None,
+            }
+        }
+    }
+
+    impl ToCommand for Protein {
+        fn to_command(&self) -> String {
+            match self {
+                Protein::A => "A",
+                Protein::B => "B",
+                Protein::C => "C",
+                Protein::D => "D",
+            }
+            .into()
+        }
+    }
+
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
     pub enum Direction {
         N,
@@ -1290,6 +3395,27 @@ pub mod atome {
         pub fn all() -> [Direction; 4] {
             [Direction::N, Direction::S, Direction::E, Direction::W]
         }
+
+        pub fn opposee(self) -> Self {
+            match self {
+                Direction::N => Direction::S,
+                Direction::S => Direction::N,
+                Direction::E => Direction::W,
+                Direction::W => Direction::E,
+            }
+        }
+    }
+
+    impl FromCommand for Direction {
+        fn from_command(commande: &str) -> Option<Self> {
+            match commande {
+                "N" => Some(Direction::N),
+                "E" => Some(Direction::E),
+                "S" => Some(Direction::S),
+                "W" => Some(Direction::W),
+                _ => None,
+            }
+        }
     }
 
     impl ToCommand for Direction {
@@ -1323,6 +3449,19 @@ pub mod atome {
             }
         }
     }
+    impl FromCommand for OrganeType {
+        fn from_command(commande: &str) -> Option<Self> {
+            match commande {
+                "ROOT" => Some(OrganeType::Root),
+                "BASIC" => Some(OrganeType::Basic),
+                "HARVESTER" => Some(OrganeType::Harvester),
+                "TENTACLE" => Some(OrganeType::Tentacle),
+                "SPORER" => Some(OrganeType::Sporer),
+                _ => None,
+            }
+        }
+    }
+
     impl ToCommand for OrganeType {
         fn to_command(&self) -> String {
             match self {
@@ -1339,11 +3478,11 @@ pub mod atome {
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
     pub struct Id {
         owner: Owner,
-        id: u8,
+        id: u16,
     }
 
     impl Id {
-        pub fn new(owner: Owner, id: u8) -> Self {
+        pub fn new(owner: Owner, id: u16) -> Self {
             Id { owner, id }
         }
         pub fn zero(owner: Owner) -> Self {
@@ -1356,7 +3495,7 @@ pub mod atome {
             }
         }
 
-        pub fn get_num(&self) -> u8 {
+        pub fn get_num(&self) -> u16 {
             self.id
         }
     }
@@ -1397,11 +3536,7 @@ pub mod atome {
 
     impl Cell {
         pub fn can_grow(&self) -> bool {
-            match self.entity {
-                Entity::Void => true,
-                Entity::Protein(_) => true,
-                _ => false,
-            }
+            matches!(self.entity, Entity::Void | Entity::Protein(_))
         }
     }
 
@@ -1457,29 +3592,69 @@ pub mod atome {
         pub width: u8,
     }
 
+    impl Dimension {
+        pub fn coords(&self) -> impl Iterator<Item = Coord> {
+            let width = self.width;
+            (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord { x, y }))
+        }
+
+        pub fn contient(&self, coord: Coord) -> bool {
+            coord.x < self.width && coord.y < self.height
+        }
+    }
+
+    /// Stock de protéines. `+`, `-` et `*` saturent; `checked_*` signalent le dépassement.
+    /// L'ordre est partiel, case par case: `stock >= prix` veut dire que l'on peut payer.
     #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Default)]
     pub struct Ressource {
-        a: u8,
-        b: u8,
-        c: u8,
-        d: u8,
+        a: u32,
+        b: u32,
+        c: u32,
+        d: u32,
     }
 
     impl Ressource {
-        pub fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
+        pub fn new(a: u32, b: u32, c: u32, d: u32) -> Self {
             Self { a, b, c, d }
         }
 
+        fn combiner(self, rhs: Self, f: impl Fn(u32, u32) -> u32) -> Self {
+            Self {
+                a: f(self.a, rhs.a),
+                b: f(self.b, rhs.b),
+                c: f(self.c, rhs.c),
+                d: f(self.d, rhs.d),
+            }
+        }
+
+        fn combiner_checked(self, rhs: Self, f: impl Fn(u32, u32) -> Option<u32>) -> Option<Self> {
+            Some(Self {
+                a: f(self.a, rhs.a)?,
+                b: f(self.b, rhs.b)?,
+                c: f(self.c, rhs.c)?,
+                d: f(self.d, rhs.d)?,
+            })
+        }
+
+        pub fn checked_add(self, rhs: Self) -> Option<Self> {
+            self.combiner_checked(rhs, u32::checked_add)
+        }
+
+        /// `None` si une protéine manque: c'est le paiement d'un prix.
         pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+            self.combiner_checked(rhs, u32::checked_sub)
+        }
+
+        pub fn checked_mul(self, nb: u32) -> Option<Self> {
             Some(Self {
-                a: self.a.checked_sub(rhs.a)?,
-                b: self.b.checked_sub(rhs.b)?,
-                c: self.c.checked_sub(rhs.c)?,
-                d: self.d.checked_sub(rhs.d)?,
+                a: self.a.checked_mul(nb)?,
+                b: self.b.checked_mul(nb)?,
+                c: self.c.checked_mul(nb)?,
+                d: self.d.checked_mul(nb)?,
             })
         }
 
-        pub fn get(&self, prot: &Protein) -> u8 {
+        pub fn get(&self, prot: &Protein) -> u32 {
             match prot {
                 Protein::A => self.a,
                 Protein::B => self.b,
@@ -1488,30 +3663,78 @@ pub mod atome {
             }
         }
 
-        fn ajout(mut self, prot: Protein, nb: u8) -> Self {
+        pub fn total(&self) -> u32 {
+            self.a
+                .saturating_add(self.b)
+                .saturating_add(self.c)
+                .saturating_add(self.d)
+        }
+
+        fn seule(prot: Protein, nb: u32) -> Self {
+            let mut ressource = Self::default();
             match prot {
-                Protein::A => {
-                    self.a = self.a.saturating_add(nb);
-                }
-                Protein::B => {
-                    self.b = self.b.saturating_add(nb);
-                }
-                Protein::C => {
-                    self.c = self.c.saturating_add(nb);
-                }
-                Protein::D => {
-                    self.d = self.d.saturating_add(nb);
-                }
+                Protein::A => ressource.a = nb,
+                Protein::B => ressource.b = nb,
+                Protein::C => ressource.c = nb,
+                Protein::D => ressource.d = nb,
             };
-            self
+            ressource
         }
 
         pub fn ajout_1(self, prot: Protein) -> Self {
-            self.ajout(prot, 1)
+            self + Self::seule(prot, 1)
         }
 
         pub fn ajout_3(self, prot: Protein) -> Self {
-            self.ajout(prot, 3)
+            self + Self::seule(prot, 3)
+        }
+    }
+
+    impl Add for Ressource {
+        type Output = Self;
+
+        fn add(self, rhs: Self) -> Self {
+            self.combiner(rhs, u32::saturating_add)
+        }
+    }
+
+    impl Sub for Ressource {
+        type Output = Self;
+
+        fn sub(self, rhs: Self) -> Self {
+            self.combiner(rhs, u32::saturating_sub)
+        }
+    }
+
+    impl Mul<u32> for Ressource {
+        type Output = Self;
+
+        fn mul(self, nb: u32) -> Self {
+            Self {
+                a: self.a.saturating_mul(nb),
+                b: self.b.saturating_mul(nb),
+                c: self.c.saturating_mul(nb),
+                d: self.d.saturating_mul(nb),
+            }
+        }
+    }
+
+    impl PartialOrd for Ressource {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            let [a, b, c, d] = [
+                self.a.cmp(&other.a),
+                self.b.cmp(&other.b),
+                self.c.cmp(&other.c),
+                self.d.cmp(&other.d),
+            ];
+            [b, c, d]
+                .into_iter()
+                .try_fold(a, |ordre, suivant| match (ordre, suivant) {
+                    (ordre, Ordering::Equal) => Some(ordre),
+                    (Ordering::Equal, suivant) => Some(suivant),
+                    (ordre, suivant) if ordre == suivant => Some(ordre),
+                    _ => None,
+                })
         }
     }
 
@@ -1565,6 +3788,30 @@ pub mod atome {
             self
         }
 
+        /// Les coups de l'ennemi sont simulés mais ne font pas partie de notre planification.
+        pub fn add_decision_for(self, owner: Owner, decision: Decision) -> Self {
+            match owner {
+                Owner::Me => self.add_decision(decision),
+                Owner::Ennemy => self,
+            }
+        }
+
+        pub fn from_content(content: Vec<Vec<Decision>>) -> Self {
+            if content.is_empty() {
+                Self::default()
+            } else {
+                Self { content }
+            }
+        }
+
+        /// Retire le tour joué: le deuxième tour devient le premier.
+        pub fn decaler(mut self) -> Self {
+            if !self.content.is_empty() {
+                self.content.remove(0);
+            }
+            Self::from_content(self.content)
+        }
+
         pub fn take_first_turn(self) -> Vec<Decision> {
             self.content.into_iter().next().unwrap_or_default()
         }
@@ -1583,129 +3830,467 @@ pub mod atome {
     }
 }
 
-mod parsing {
-    use std::io;
+pub mod parsing {
+    use std::{
+        fmt::Display,
+        io::BufRead,
+        num::{IntErrorKind, ParseIntError},
+        str::{FromStr, Split},
+    };
+
+    use super::{atome::*, replay::Enregistreur, state::InitState};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ParseError {
+        pub ligne: usize,
+        pub champ: &'static str,
+        pub brut: String,
+        pub cause: Cause,
+    }
 
-    use super::{atome::*, state::InitState};
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum Cause {
+        #[default]
+        Illisible,
+        /// Un nombre bien écrit mais trop grand pour son type.
+        HorsBornes,
+    }
 
-    pub fn parser_dimension() -> Dimension {
-        let mut buf = String::new();
-        io::stdin().read_line(&mut buf).unwrap();
-        let mut inputs = buf.split(" ").map(str::trim).map(str::parse);
-        Dimension {
-            width: inputs.next().expect("pas de width").unwrap_or(u8::MAX),
-            height: inputs.next().expect("pas de height").unwrap_or(u8::MAX),
+    impl Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let cause = match self.cause {
+                Cause::Illisible => "illisible",
+                Cause::HorsBornes => "hors bornes",
+            };
+            write!(
+                f,
+                "ligne {}: {} {} dans {:?}",
+                self.ligne, self.champ, cause, self.brut
+            )
         }
     }
 
-    fn parser_count() -> u32 {
-        let mut buf = String::new();
-        io::stdin().read_line(&mut buf).unwrap();
-        let mut inputs = buf.split(" ").map(str::trim).map(str::parse);
-        inputs
-            .next()
-            .expect("pas de entitycount")
-            .expect("entityCount pas un nombre")
+    pub struct Lecteur<R> {
+        entree: R,
+        ligne: usize,
+        fini: bool,
+        enregistreur: Option<Enregistreur>,
     }
 
-    fn parser_entity() -> Cell {
-        let mut buf = String::new();
-        io::stdin().read_line(&mut buf).unwrap();
-        let mut inputs = buf.split(" ").map(str::trim);
-        let x = inputs
-            .next()
-            .expect("pas de x")
-            .parse()
-            .expect("x pas un nombre");
-        let y = inputs
-            .next()
-            .expect("pas de y")
-            .parse()
-            .expect("y pas un nombre");
-        let type_str: &str = inputs.next().expect("pas de type");
-        let owner_opt: Option<Owner> = match inputs.next().expect("pas d'owner") {
+    impl<R: BufRead> Lecteur<R> {
+        pub fn new(entree: R) -> Self {
+            Self {
+                entree,
+                ligne: 0,
+                fini: false,
+                enregistreur: None,
+            }
+        }
+
+        /// Vrai une fois l'entrée épuisée: la partie est terminée.
+        pub fn est_fini(&self) -> bool {
+            self.fini
+        }
+
+        pub fn with_enregistreur(mut self, enregistreur: Enregistreur) -> Self {
+            self.enregistreur = Some(enregistreur);
+            self
+        }
+
+        pub fn enregistreur(&mut self) -> Option<&mut Enregistreur> {
+            self.enregistreur.as_mut()
+        }
+
+        fn lire_ligne(&mut self, champ: &'static str) -> Result<Ligne, ParseError> {
+            let mut buf = String::new();
+            self.ligne += 1;
+            let erreur = |brut: String| ParseError {
+                ligne: self.ligne,
+                champ,
+                brut,
+                cause: Cause::Illisible,
+            };
+            match self.entree.read_line(&mut buf) {
+                Ok(0) => {
+                    self.fini = true;
+                    Err(erreur(String::new()))
+                }
+                Ok(_) => {
+                    let brut = buf.trim_end_matches(['\n', '\r']).to_string();
+                    if let Some(enregistreur) = self.enregistreur.as_mut() {
+                        enregistreur.entree(&brut);
+                    }
+                    Ok(Ligne {
+                        numero: self.ligne,
+                        brut,
+                    })
+                }
+                Err(e) => Err(erreur(e.to_string())),
+            }
+        }
+    }
+
+    struct Ligne {
+        numero: usize,
+        brut: String,
+    }
+
+    impl Ligne {
+        fn erreur(&self, champ: &'static str) -> ParseError {
+            ParseError {
+                ligne: self.numero,
+                champ,
+                brut: self.brut.clone(),
+                cause: Cause::Illisible,
+            }
+        }
+
+        fn mots(&self) -> Mots<'_> {
+            Mots {
+                ligne: self,
+                mots: self.brut.split(' '),
+            }
+        }
+    }
+
+    struct Mots<'a> {
+        ligne: &'a Ligne,
+        mots: Split<'a, char>,
+    }
+
+    impl<'a> Mots<'a> {
+        fn texte(&mut self, champ: &'static str) -> Result<&'a str, ParseError> {
+            self.mots
+                .next()
+                .map(str::trim)
+                .ok_or_else(|| self.ligne.erreur(champ))
+        }
+
+        fn nombre<T: FromStr>(&mut self, champ: &'static str) -> Result<T, ParseError> {
+            self.texte(champ)?
+                .parse()
+                .map_err(|_| self.ligne.erreur(champ))
+        }
+
+        fn quantite(&mut self, champ: &'static str) -> Result<u32, ParseError> {
+            self.texte(champ)?
+                .parse()
+                .map_err(|e: ParseIntError| match e.kind() {
+                    IntErrorKind::PosOverflow => ParseError {
+                        cause: Cause::HorsBornes,
+                        ..self.ligne.erreur(champ)
+                    },
+                    _ => self.ligne.erreur(champ),
+                })
+        }
+    }
+
+    pub fn parser_dimension<R: BufRead>(lecteur: &mut Lecteur<R>) -> Result<Dimension, ParseError> {
+        let ligne = lecteur.lire_ligne("dimension")?;
+        let mut mots = ligne.mots();
+        Ok(Dimension {
+            width: mots.nombre("width")?,
+            height: mots.nombre("height")?,
+        })
+    }
+
+    fn parser_count<R: BufRead>(
+        lecteur: &mut Lecteur<R>,
+        champ: &'static str,
+    ) -> Result<u32, ParseError> {
+        let ligne = lecteur.lire_ligne(champ)?;
+        let count = ligne.mots().nombre(champ)?;
+        Ok(count)
+    }
+
+    pub fn parser_entity<R: BufRead>(lecteur: &mut Lecteur<R>) -> Result<Cell, ParseError> {
+        let ligne = lecteur.lire_ligne("entity")?;
+        let mut mots = ligne.mots();
+        let x = mots.nombre("x")?;
+        let y = mots.nombre("y")?;
+        let type_str = mots.texte("type")?;
+        let owner_opt: Option<Owner> = match mots.texte("owner")? {
             "1" => Some(Owner::Me),
             "0" => Some(Owner::Ennemy),
             _ => None,
         };
-        let organe_id = inputs
-            .next()
-            .expect("pas d'organ_id")
-            .parse()
-            .expect("organ_id pas un nombre");
-        let organe_dir: Option<Direction> = match inputs.next().expect("pas de direction") {
-            "N" => Some(Direction::N),
-            "E" => Some(Direction::E),
-            "S" => Some(Direction::S),
-            "W" => Some(Direction::W),
-            _ => None,
-        };
-        let organe_parent_id = inputs
-            .next()
-            .expect("pas d'organ_parent_id")
-            .parse()
-            .expect("organ_parent_id pas un nombre");
-        let organe_root_id = inputs
-            .next()
-            .expect("pas d'organ_root_id")
-            .parse()
-            .expect("organ_root_id pas un nombre");
+        let organe_id = mots.nombre("organId")?;
+        let organe_dir = Direction::from_command(mots.texte("organDir")?);
+        let organe_parent_id = mots.nombre("organParentId")?;
+        let organe_root_id = mots.nombre("organRootId")?;
         let entity: Entity = match type_str {
             "WALL" => Entity::Wall,
-            "A" => Entity::Protein(Protein::A),
-            "B" => Entity::Protein(Protein::B),
-            "C" => Entity::Protein(Protein::C),
-            "D" => Entity::Protein(Protein::D),
-            organ_type_str => {
-                let organe_type: OrganeType = match organ_type_str {
-                    "ROOT" => OrganeType::Root,
-                    "BASIC" => OrganeType::Basic,
-                    "HARVESTER" => OrganeType::Harvester,
-                    "TENTACLE" => OrganeType::Tentacle,
-                    "SPORER" => OrganeType::Sporer,
-                    _ => panic!("pas d'organe type valide: {organ_type_str}"),
-                };
-                let owner = owner_opt.expect("pas d'owner");
-                Entity::Organe(Organe {
-                    organe_type,
-                    dir: organe_dir.expect("pas de dir"),
-                    owner,
-                    id: Id::new(owner, organe_id),
-                    parent_id: Id::new(owner, organe_parent_id),
-                    root_id: Id::new(owner, organe_root_id),
-                })
-            }
+            type_str => match Protein::from_command(type_str) {
+                Some(prot) => Entity::Protein(prot),
+                None => {
+                    let organe_type =
+                        OrganeType::from_command(type_str).ok_or_else(|| ligne.erreur("type"))?;
+                    let owner = owner_opt.ok_or_else(|| ligne.erreur("owner"))?;
+                    Entity::Organe(Organe {
+                        organe_type,
+                        dir: organe_dir.ok_or_else(|| ligne.erreur("organDir"))?,
+                        owner,
+                        id: Id::new(owner, organe_id),
+                        parent_id: Id::new(owner, organe_parent_id),
+                        root_id: Id::new(owner, organe_root_id),
+                    })
+                }
+            },
         };
-        Cell {
+        Ok(Cell {
             coord: Coord { x, y },
             entity,
+        })
+    }
+
+    pub fn parser_resource<R: BufRead>(lecteur: &mut Lecteur<R>) -> Result<Ressource, ParseError> {
+        let ligne = lecteur.lire_ligne("proteines")?;
+        let mut mots = ligne.mots();
+        Ok(Ressource::new(
+            mots.quantite("proteine A")?,
+            mots.quantite("proteine B")?,
+            mots.quantite("proteine C")?,
+            mots.quantite("proteine D")?,
+        ))
+    }
+
+    pub fn parser_tour<R: BufRead>(
+        lecteur: &mut Lecteur<R>,
+        dimension: Dimension,
+    ) -> Result<InitState, ParseError> {
+        let entity_count = parser_count(lecteur, "entityCount")?;
+        // Tout le tour est lu avant de signaler une erreur: le tour suivant reste lisible.
+        let cells: Vec<Result<Cell, ParseError>> = (0u32..entity_count)
+            .map(|_| parser_entity(lecteur))
+            .collect();
+        let ressources_ami = parser_resource(lecteur);
+        let ressources_ennemy = parser_resource(lecteur);
+        let action_count = parser_count(lecteur, "requiredActionsCount");
+        Ok(InitState::new(
+            dimension,
+            ressources_ami?,
+            ressources_ennemy?,
+            ActionCount::new(action_count?),
+            cells.into_iter().collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+pub mod replay {
+    use std::io::{BufRead, Cursor, Write};
+
+    use super::{
+        atome::*,
+        decision::Decision,
+        parsing::{parser_dimension, parser_tour, Cause, Lecteur, ParseError},
+        state::InitState,
+    };
+
+    const ENTREE: &str = "> ";
+    const SORTIE: &str = "< ";
+    const TOUR: &str = "# tour ";
+
+    /// Écrit un bloc par tour: `# tour N`, les entrées après `> `, les sorties après `< `.
+    pub struct Enregistreur {
+        sortie: Box<dyn Write>,
+        tour: u32,
+    }
+
+    impl Enregistreur {
+        pub fn new(sortie: Box<dyn Write>) -> Self {
+            Self { sortie, tour: 0 }
+        }
+
+        // Une erreur d'écriture ne doit pas arrêter le bot: l'enregistrement est au mieux.
+        fn ecrire(&mut self, prefixe: &str, ligne: &str) {
+            let _ = writeln!(self.sortie, "{prefixe}{ligne}");
+        }
+
+        pub fn nouveau_tour(&mut self) {
+            let tour = self.tour.to_string();
+            self.ecrire(TOUR, &tour);
+            self.tour += 1;
+        }
+
+        pub fn entree(&mut self, ligne: &str) {
+            self.ecrire(ENTREE, ligne);
+        }
+
+        pub fn sortie(&mut self, commande: &str) {
+            self.ecrire(SORTIE, commande);
+            let _ = self.sortie.flush();
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct TourEnregistre {
+        pub state: InitState,
+        pub decisions: Vec<Decision>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Replay {
+        pub dimension: Dimension,
+        pub tours: Vec<TourEnregistre>,
+    }
+
+    #[derive(Default)]
+    struct Bloc {
+        entrees: Vec<(usize, String)>,
+        sorties: Vec<String>,
+    }
+
+    /// Relit un fichier écrit par `Enregistreur`; les erreurs portent ses numéros de ligne.
+    pub fn charger_replay<R: BufRead>(entree: R) -> Result<Replay, ParseError> {
+        let mut entete = Bloc::default();
+        let mut blocs: Vec<Bloc> = Vec::new();
+        for (indice, ligne) in entree.lines().enumerate() {
+            let numero = indice + 1;
+            let ligne = ligne.map_err(|e| ParseError {
+                ligne: numero,
+                champ: "replay",
+                brut: e.to_string(),
+                cause: Cause::Illisible,
+            })?;
+            let bloc = blocs.last_mut().unwrap_or(&mut entete);
+            if ligne.starts_with(TOUR) {
+                blocs.push(Bloc::default());
+            } else if let Some(entree) = ligne.strip_prefix(ENTREE) {
+                bloc.entrees.push((numero, entree.to_string()));
+            } else if let Some(sortie) = ligne.strip_prefix(SORTIE) {
+                bloc.sorties.push(sortie.to_string());
+            }
         }
+
+        let dimension = relire(&entete, parser_dimension)?;
+        // Le dernier bloc est vide quand la partie s'arrête pendant la lecture d'un tour.
+        let tours = blocs
+            .iter()
+            .filter(|bloc| !bloc.entrees.is_empty())
+            .map(|bloc| {
+                let state = relire(bloc, |lecteur| parser_tour(lecteur, dimension))?;
+                let decisions = bloc
+                    .sorties
+                    .iter()
+                    .map(|commande| Decision::from_command(commande, Owner::Me).unwrap_or_default())
+                    .collect();
+                Ok(TourEnregistre { state, decisions })
+            })
+            .collect::<Result<_, ParseError>>()?;
+        Ok(Replay { dimension, tours })
+    }
+
+    fn relire<T, F>(bloc: &Bloc, parser: F) -> Result<T, ParseError>
+    where
+        F: FnOnce(&mut Lecteur<Cursor<String>>) -> Result<T, ParseError>,
+    {
+        let texte: String = bloc
+            .entrees
+            .iter()
+            .map(|(_, ligne)| format!("{ligne}\n"))
+            .collect();
+        parser(&mut Lecteur::new(Cursor::new(texte))).map_err(|erreur| ParseError {
+            ligne: bloc
+                .entrees
+                .get(erreur.ligne.saturating_sub(1))
+                .or(bloc.entrees.last())
+                .map_or(0, |(numero, _)| *numero),
+            ..erreur
+        })
     }
+}
 
-    fn parser_resource() -> Ressource {
-        let mut buf = String::new();
-        io::stdin().read_line(&mut buf).unwrap();
-        let mut inputs = buf.split(" ").map(str::trim).map(str::parse);
-        let a = inputs.next().expect("pas de proteine A").unwrap_or(u8::MAX);
+pub mod rendu {
+    use std::fmt::{Display, Formatter, Result};
+
+    use super::{atome::*, molecule::*, state::State};
+
+    pub const LETTRES_ORGANE: [(char, OrganeType); 5] = [
+        ('r', OrganeType::Root),
+        ('b', OrganeType::Basic),
+        ('h', OrganeType::Harvester),
+        ('t', OrganeType::Tentacle),
+        ('s', OrganeType::Sporer),
+    ];
+
+    pub const FLECHES: [(char, Direction); 4] = [
+        ('^', Direction::N),
+        ('>', Direction::E),
+        ('v', Direction::S),
+        ('<', Direction::W),
+    ];
+
+    pub const RECOLTE: char = '*';
+    pub const CIBLE: char = '!';
+
+    /// Deux caractères par case: minuscule pour nous, majuscule pour l'ennemi, puis la direction.
+    pub fn glyphe(entity: Entity) -> String {
+        match entity {
+            Entity::Void => ". ".to_string(),
+            Entity::Wall => "# ".to_string(),
+            Entity::Protein(prot) => format!("{} ", prot.to_command()),
+            Entity::Organe(org) => {
+                let lettre = LETTRES_ORGANE
+                    .iter()
+                    .find(|(_, t)| *t == org.organe_type)
+                    .map_or('?', |(l, _)| *l);
+                let lettre = match org.owner {
+                    Owner::Me => lettre,
+                    Owner::Ennemy => lettre.to_ascii_uppercase(),
+                };
+                let fleche = FLECHES
+                    .iter()
+                    .find(|(_, d)| *d == org.dir)
+                    .map_or('?', |(f, _)| *f);
+                format!("{lettre}{fleche}")
+            }
+        }
+    }
 
-        let b = inputs.next().expect("pas de proteine B").unwrap_or(u8::MAX);
-        let c = inputs.next().expect("pas de proteine C").unwrap_or(u8::MAX);
-        let d = inputs.next().expect("pas de proteine D").unwrap_or(u8::MAX);
-        Ressource::new(a, b, c, d)
+    pub fn ecrire_ressources(
+        f: &mut impl std::fmt::Write,
+        ressources: OwnerMap<Ressource>,
+    ) -> Result {
+        for (cle, owner) in [("ami", Owner::Me), ("ennemi", Owner::Ennemy)] {
+            let stock = ressources.get(owner);
+            let stock: Vec<String> = Protein::all()
+                .iter()
+                .map(|p| stock.get(p).to_string())
+                .collect();
+            writeln!(f, "{cle}: {}", stock.join(" "))?;
+        }
+        Ok(())
     }
 
-    pub fn parser_tour(dimension: Dimension) -> InitState {
-        let entity_count = parser_count();
-        let cells: Vec<Cell> = (0u32..entity_count).map(|_| parser_entity()).collect();
-        let ressources_ami = parser_resource();
-        let ressources_ennemy = parser_resource();
-        let action_count = ActionCount::new(parser_count());
-        InitState::new(
-            dimension,
-            ressources_ami,
-            ressources_ennemy,
-            action_count,
-            cells,
-        )
+    /// Affiche un état intermédiaire: `*` après une case récoltée, `!` après une cible de tentacle.
+    pub struct Rendu<'a>(pub &'a dyn State);
+
+    impl Display for Rendu<'_> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+            let state = self.0;
+            ecrire_ressources(f, state.ressource())?;
+            let harvesting = state.harvesting();
+            let cibles: Vec<Coord> = state.attacking().values().map(|a| a.target_coord).collect();
+            let dimension = state.dimension();
+            for y in 0..dimension.height {
+                write!(f, "|")?;
+                for x in 0..dimension.width {
+                    let coord = Coord { x, y };
+                    let entity = state.get_by_coord(coord).map_or(Entity::Void, |c| c.entity);
+                    let marque = if cibles.contains(&coord) {
+                        CIBLE
+                    } else if harvesting.get(Owner::Me).contains_key(&coord)
+                        || harvesting.get(Owner::Ennemy).contains_key(&coord)
+                    {
+                        RECOLTE
+                    } else {
+                        ' '
+                    };
+                    write!(f, "{}{marque}|", glyphe(entity))?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,179 @@
+use codingame::arbitre::*;
+use codingame::common::*;
+use codingame::ligue1::{atome::*, decision::*, molecule::OwnerMap, state::*};
+use rand::{rngs::StdRng, SeedableRng};
+
+struct Script {
+    commande: &'static str,
+}
+
+impl Joueur for Script {
+    fn jouer(
+        &mut self,
+        _entree: &str,
+        nb_actions: usize,
+        _tour: u32,
+    ) -> Result<Vec<String>, ErreurJoueur> {
+        Ok(vec![self.commande.to_string(); nb_actions])
+    }
+}
+
+struct Muet;
+
+impl Joueur for Muet {
+    fn jouer(
+        &mut self,
+        _entree: &str,
+        _nb_actions: usize,
+        _tour: u32,
+    ) -> Result<Vec<String>, ErreurJoueur> {
+        Err(ErreurJoueur::Delai)
+    }
+}
+
+#[test]
+fn carte_symetrique() {
+    let carte = generer_carte(&mut StdRng::seed_from_u64(7));
+    let Dimension { width, height } = carte.dimension();
+    for coord in carte.dimension().coords() {
+        let miroir = Coord {
+            x: width - 1 - coord.x,
+            y: height - 1 - coord.y,
+        };
+        let entity = carte.get_by_coord(coord).map(|c| c.entity);
+        let entity_miroir = carte.get_by_coord(miroir).map(|c| c.entity);
+        match (entity, entity_miroir) {
+            (Some(Entity::Organe(a)), Some(Entity::Organe(b))) => {
+                assert_eq!(a.owner, b.owner.switch_side())
+            }
+            (a, b) => assert_eq!(a, b),
+        }
+    }
+    assert_eq!(carte.nb_organe(Owner::Me), 1);
+    assert_eq!(carte.nb_organe(Owner::Ennemy), 1);
+}
+
+#[test]
+fn entree_du_point_de_vue_de_chaque_joueur() {
+    let ennemy = OrganismBuilder::default().build(Owner::Ennemy, Coord { x: 2, y: 0 });
+    let state = StateBuilder::new_ligne_de_3_root_a_gauche()
+        .with_ressources_ennemy(Ressource::new(5, 6, 7, 8))
+        .add_cells(ennemy)
+        .build();
+
+    assert_eq!(entree_initiale(state.dimension()), "3 1\n");
+    assert_eq!(
+        entree_tour(&state, Owner::Me),
+        "2\n0 0 ROOT 1 0 N 0 0\n2 0 ROOT 0 0 N 0 0\n1 1 1 1\n5 6 7 8\n1\n"
+    );
+    assert_eq!(
+        entree_tour(&state, Owner::Ennemy),
+        "2\n0 0 ROOT 0 0 N 0 0\n2 0 ROOT 1 0 N 0 0\n5 6 7 8\n1 1 1 1\n1\n"
+    );
+}
+
+#[test]
+fn commande_relue() {
+    let grow = Decision::Grow(Grow {
+        parent_id: Id::new(Owner::Ennemy, 4),
+        coord: Coord { x: 3, y: 7 },
+        organe_type: OrganeType::Harvester,
+        direction: Direction::W,
+    });
+    let spore = Decision::Spore(Spore {
+        parent_id: Id::new(Owner::Ennemy, 2),
+        coord: Coord { x: 10, y: 1 },
+    });
+    for decision in [grow, spore, Decision::Wait] {
+        assert_eq!(
+            Decision::from_command(&decision.to_command(), Owner::Ennemy),
+            Some(decision)
+        );
+    }
+    assert_eq!(
+        Decision::from_command("WAIT on attend", Owner::Me),
+        Some(Decision::Wait)
+    );
+    assert_eq!(Decision::from_command("GROW 1 2", Owner::Me), None);
+}
+
+#[test]
+fn partie_jusqu_au_dernier_tour() {
+    let carte = generer_carte(&mut StdRng::seed_from_u64(3));
+    let resultat = arbitrer(
+        carte,
+        &mut Script { commande: "WAIT" },
+        &mut Script { commande: "WAIT" },
+    );
+    assert_eq!(resultat.nb_tours, NB_TOURS_MAX);
+    assert_eq!(resultat.gagnant, None);
+    assert_eq!(resultat.forfait, None);
+}
+
+#[test]
+fn a_organes_egaux_les_proteines_departagent() {
+    let state = StateBuilder::from_ascii(
+        "ami: 1 1 0 0\n\
+         ennemi: 0 0 0 3\n\
+         |r>|. |. |R<|\n",
+    )
+    .unwrap()
+    .build();
+    let resultat = arbitrer(
+        state,
+        &mut Script { commande: "WAIT" },
+        &mut Script { commande: "WAIT" },
+    );
+    assert_eq!(resultat.nb_tours, NB_TOURS_MAX);
+    assert_eq!(resultat.nb_organes, OwnerMap::new(1, 1));
+    assert_eq!(resultat.gagnant, Some(Owner::Ennemy));
+}
+
+#[test]
+fn forfait_sur_delai() {
+    let carte = generer_carte(&mut StdRng::seed_from_u64(3));
+    let resultat = arbitrer(carte, &mut Script { commande: "WAIT" }, &mut Muet);
+    assert_eq!(resultat.nb_tours, 0);
+    assert_eq!(resultat.gagnant, Some(Owner::Me));
+    assert_eq!(resultat.forfait, Some((Owner::Ennemy, ErreurJoueur::Delai)));
+}
+
+fn ids(state: &dyn State) -> Vec<u16> {
+    let mut ids: Vec<u16> = state
+        .dimension()
+        .coords()
+        .filter_map(|coord| match state.get_by_coord(coord)?.entity {
+            Entity::Organe(organe) => Some(organe.id.get_num()),
+            _ => None,
+        })
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn un_seul_compteur_d_id_pour_les_deux_joueurs() {
+    let carte = generer_carte(&mut StdRng::seed_from_u64(3));
+    assert_eq!(ids(&carte), vec![1, 2]);
+
+    let grow = |owner: Owner| {
+        let root_id = *carte.roots().get(owner).iter().next().unwrap();
+        let root = carte.get_by_id(root_id).unwrap();
+        let coord = carte
+            .get_neighbour(root.coord)
+            .into_iter()
+            .flatten()
+            .find(|cell| !matches!(cell.entity, Entity::Organe(_) | Entity::Wall))
+            .unwrap()
+            .coord;
+        vec![Decision::Grow(Grow {
+            parent_id: root_id,
+            coord,
+            organe_type: OrganeType::Basic,
+            direction: Direction::N,
+        })]
+    };
+    let decisions = OwnerMap::new(grow(Owner::Me), grow(Owner::Ennemy));
+    let suite = simuler_tour(&carte, decisions);
+    assert_eq!(ids(&suite), vec![1, 2, 3, 4]);
+}
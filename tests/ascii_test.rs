@@ -0,0 +1,106 @@
+use codingame::common::*;
+use codingame::ligue1::{atome::*, state::*};
+
+#[test]
+fn meme_etat_que_le_builder() {
+    let carte = "ami: 50 1 1 1\n\
+                 ennemi: 50 50 50 50\n\
+                 |. |. |. |. |. |\n\
+                 |. |. |. |. |. |\n\
+                 |r^|. |. |. |A |\n\
+                 |. |. |. |. |. |\n\
+                 |. |. |. |. |. |\n";
+    let state = StateBuilder::from_ascii(carte).unwrap().build();
+    let attendu = StateBuilder::new_a_gauche_prot_a_a_droite().build();
+    assert_eq!(
+        state.get_by_coord(Coord { x: 0, y: 2 }),
+        attendu.get_by_coord(Coord { x: 0, y: 2 })
+    );
+    assert_eq!(
+        state.get_by_coord(Coord { x: 4, y: 2 }),
+        attendu.get_by_coord(Coord { x: 4, y: 2 })
+    );
+    assert_eq!(state.ressource(), attendu.ressource());
+    assert_eq!(state.dimension(), attendu.dimension());
+    assert_eq!(dessiner(&state), dessiner(&attendu));
+}
+
+#[test]
+fn ids_et_parents_deduits() {
+    let carte = "ami: 1 1 1 1\n\
+                 ennemi: 1 1 1 1\n\
+                 ----------------\n\
+                 |r>|b>|h>|A |# |\n\
+                 |. |t^|. |B<|R<|\n";
+    let state = StateBuilder::from_ascii(carte).unwrap().build();
+    let organe = |x, y| match state.get_by_coord(Coord { x, y }).map(|c| c.entity) {
+        Some(Entity::Organe(org)) => org,
+        autre => panic!("pas un organe: {autre:?}"),
+    };
+
+    let racine = organe(0, 0);
+    assert_eq!(racine.parent_id, Id::zero(Owner::Me));
+    assert_eq!(racine.root_id, racine.id);
+    assert_eq!(organe(1, 0).parent_id, racine.id);
+    assert_eq!(organe(2, 0).parent_id, organe(1, 0).id);
+    assert_eq!(organe(1, 1).parent_id, organe(1, 0).id);
+    assert_eq!(organe(1, 1).organe_type, OrganeType::Tentacle);
+    assert_eq!(organe(3, 1).owner, Owner::Ennemy);
+    assert_eq!(organe(3, 1).dir, Direction::W);
+    assert_eq!(organe(3, 1).parent_id, organe(4, 1).id);
+    assert_eq!(state.organes_by_root(racine.id).len(), 4);
+    assert_eq!(state.action_set().get(Owner::Me).len(), 1);
+
+    let relu = StateBuilder::from_ascii(&dessiner(&state)).unwrap().build();
+    assert_eq!(relu, state);
+}
+
+#[test]
+fn erreurs_de_carte() {
+    assert_eq!(
+        StateBuilder::from_ascii("ami: 1 1 1\n|. |\n").unwrap_err(),
+        ErreurCarte::Entete {
+            ligne: 1,
+            brut: "ami: 1 1 1".to_string()
+        }
+    );
+    assert_eq!(
+        StateBuilder::from_ascii("|. |x |\n").unwrap_err(),
+        ErreurCarte::Glyphe {
+            ligne: 1,
+            glyphe: "x ".to_string()
+        }
+    );
+    assert_eq!(
+        StateBuilder::from_ascii("|. |. |\n|. |\n").unwrap_err(),
+        ErreurCarte::LargeurIrreguliere { ligne: 2 }
+    );
+    assert_eq!(
+        StateBuilder::from_ascii("|r |. |b |\n").unwrap_err(),
+        ErreurCarte::Orphelin {
+            coord: Coord { x: 2, y: 0 }
+        }
+    );
+}
+
+#[test]
+fn un_point_suivi_d_un_caractere_est_refuse() {
+    for glyphe in [".x", ".>"] {
+        assert_eq!(
+            StateBuilder::from_ascii(&format!("|. |{glyphe}|\n")).unwrap_err(),
+            ErreurCarte::Glyphe {
+                ligne: 1,
+                glyphe: glyphe.to_string()
+            }
+        );
+    }
+    assert!(StateBuilder::from_ascii("|. |.  *|.!|\n").is_ok());
+}
+
+#[test]
+fn erreur_de_carte_lisible() {
+    let erreur = StateBuilder::from_ascii("|. |\n|. |x |\n").unwrap_err();
+    assert_eq!(erreur.to_string(), "ligne 2: case illisible \"x \"");
+    let erreur = StateBuilder::from_ascii("|r |. |b |\n").unwrap_err();
+    assert_eq!(erreur.to_string(), "organe en (2, 0) relié à aucune racine");
+}
@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use codingame::common::*;
+use codingame::ligue1::{atome::*, decision::*, molecule::OwnerMap, state::*};
+
+/// Notre sporer vise l'est; le tentacle ennemi en (1, 1) fait face à la case (1, 0).
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 5 5 5 5\n\
+         |s>|. |. |. |R<|\n\
+         |r^|T^|B<|B<|B<|\n",
+    )
+    .unwrap()
+    .build()
+}
+
+const MENACEE: Coord = Coord { x: 1, y: 0 };
+
+fn racine(state: &dyn State, owner: Owner) -> Id {
+    *state.roots().get(owner).iter().next().unwrap()
+}
+
+fn sporer(state: &dyn State) -> Id {
+    state
+        .organes_by_root(racine(state, Owner::Me))
+        .into_iter()
+        .find(|org| org.organe_type == OrganeType::Sporer)
+        .unwrap()
+        .id
+}
+
+#[test]
+fn la_case_en_face_du_tentacle_n_est_fertile_que_pour_lui() {
+    let state = carte();
+    let plateau = Plateau::new(&state);
+    for s in [&state as &dyn State, &plateau] {
+        assert!(!s.fertile_cell(Owner::Me).contains_key(&MENACEE));
+        assert!(s.fertile_cell(Owner::Ennemy).contains_key(&MENACEE));
+        assert!(s.grow_candidate(racine(s, Owner::Me)).is_empty());
+        assert!(s
+            .grow_candidate(racine(s, Owner::Ennemy))
+            .contains_key(&MENACEE));
+    }
+}
+
+#[test]
+fn la_spore_survole_la_case_menacee_sans_s_y_poser() {
+    let state = carte();
+    let cibles: HashSet<Coord> = state
+        .spore_candidate(racine(&state, Owner::Me))
+        .into_iter()
+        .map(|candidate| candidate.coord)
+        .collect();
+    assert_eq!(cibles, [Coord { x: 2, y: 0 }, Coord { x: 3, y: 0 }].into());
+}
+
+#[test]
+fn le_plateau_et_l_arbitre_refusent_la_pousse() {
+    let state = carte();
+    let grow = Grow {
+        parent_id: sporer(&state),
+        coord: MENACEE,
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    };
+    let mut plateau = Plateau::new(&state);
+    assert!(!plateau.grow(grow));
+    assert!(!plateau.spore(Spore {
+        parent_id: sporer(&state),
+        coord: MENACEE,
+    }));
+
+    let suivant = simuler_tour(
+        &state,
+        OwnerMap::new(vec![Decision::Grow(grow)], Vec::new()),
+    );
+    assert_eq!(
+        suivant.get_by_coord(MENACEE).map(|c| c.entity),
+        Some(Entity::Void)
+    );
+}
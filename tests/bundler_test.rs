@@ -0,0 +1,152 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use codingame::arbitre::{entree_initiale, entree_tour, generer_carte};
+use codingame::ligue1::{atome::*, decision::Decision, state::State};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn le_fichier_unique_compile_et_joue() {
+    let dossier = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("bundler");
+    std::fs::create_dir_all(&dossier).unwrap();
+    let source = dossier.join("main.rs");
+    let executable = dossier.join("bot");
+
+    let bundler = Command::new(env!("CARGO_BIN_EXE_bundler"))
+        .arg("--output")
+        .arg(&source)
+        .status()
+        .unwrap();
+    assert!(bundler.success());
+    let contenu = std::fs::read_to_string(&source).unwrap();
+    assert!(!contenu.contains("mod common"));
+    assert!(!contenu.contains("use rand::"));
+    assert!(contenu.chars().count() < 100_000, "{}", contenu.len());
+
+    let rustc = Command::new("rustc")
+        .args(["--edition", "2021", "-o"])
+        .arg(&executable)
+        .arg(&source)
+        .output()
+        .unwrap();
+    assert!(
+        rustc.status.success(),
+        "{}",
+        String::from_utf8_lossy(&rustc.stderr)
+    );
+
+    let carte = generer_carte(&mut StdRng::seed_from_u64(5));
+    let mut bot = Command::new(&executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut entree = entree_initiale(carte.dimension());
+    entree.push_str(&entree_tour(&carte, Owner::Me));
+    bot.stdin
+        .take()
+        .unwrap()
+        .write_all(entree.as_bytes())
+        .unwrap();
+    let mut ligne = String::new();
+    BufReader::new(bot.stdout.take().unwrap())
+        .read_line(&mut ligne)
+        .unwrap();
+    let _ = bot.kill();
+    let _ = bot.wait();
+    assert!(
+        Decision::from_command(&ligne, Owner::Me).is_some(),
+        "{ligne}"
+    );
+}
+
+#[test]
+fn seuls_les_use_sont_reecrits() {
+    let src = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("bundler_use");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::write(src.join("lib.rs"), "pub mod bot;\n").unwrap();
+    std::fs::write(
+        src.join("bot.rs"),
+        "use rand::{\n    rngs::StdRng,\n    SeedableRng,\n};\n\
+         // rand::rngs reste un commentaire\n\
+         pub fn main() {\n    let _ = StdRng::seed_from_u64(1);\n    \
+         println!(\"rand::x\");\n}\n",
+    )
+    .unwrap();
+
+    let sortie = Command::new(env!("CARGO_BIN_EXE_bundler"))
+        .arg("--src")
+        .arg(&src)
+        .args(["--module", "bot"])
+        .output()
+        .unwrap();
+    assert!(sortie.status.success());
+    let contenu = String::from_utf8(sortie.stdout).unwrap();
+    assert!(contenu.contains("use crate::rand::{rngs::StdRng,SeedableRng,};"));
+    assert!(!contenu.contains("reste un commentaire"));
+    assert!(contenu.contains("println!(\"rand::x\")"));
+    assert!(contenu.contains("mod rand{"));
+}
+
+#[allow(dead_code)]
+#[path = "../src/bin/bundler/vendor/rand.rs"]
+mod rand_vendu;
+
+// Le même scénario de tirages, écrit une fois pour `rand` et pour sa copie vendue.
+macro_rules! tirages {
+    ($graine:expr) => {{
+        let mut rng = StdRng::seed_from_u64($graine);
+        let poids = [0.0, 1.5, 3.0, 0.25, 0.0, 7.0];
+        let indices: Vec<usize> = (0..poids.len()).collect();
+        let mut sortie: Vec<String> = Vec::new();
+        for _ in 0..50 {
+            let mut paquet: Vec<u32> = (0..10).collect();
+            paquet.shuffle(&mut rng);
+            sortie.push(format!(
+                "{} {} {} {} {} {} {:?} {:?} {:?}",
+                rng.next_u32(),
+                rng.next_u64(),
+                rng.gen_range(0..7usize),
+                rng.gen_range(0..4),
+                rng.gen_range(3u32..=200),
+                rng.gen::<f64>(),
+                paquet,
+                indices.choose(&mut rng),
+                indices.choose_weighted(&mut rng, |i| poids[*i]),
+            ));
+            let choisis: Vec<&usize> = indices
+                .choose_multiple_weighted(&mut rng, 5, |i| poids[*i])
+                .unwrap()
+                .collect();
+            sortie.push(format!("{choisis:?}"));
+        }
+        sortie
+    }};
+}
+
+mod local {
+    use rand::{prelude::*, RngCore};
+
+    pub fn tirages(graine: u64) -> Vec<String> {
+        tirages!(graine)
+    }
+}
+
+mod vendu {
+    use super::rand_vendu::prelude::*;
+
+    pub fn tirages(graine: u64) -> Vec<String> {
+        tirages!(graine)
+    }
+}
+
+#[test]
+fn le_rand_vendu_tire_la_meme_suite_que_rand() {
+    for graine in [0, 33, 81, u64::MAX] {
+        assert_eq!(vendu::tirages(graine), local::tirages(graine));
+    }
+}
@@ -0,0 +1,120 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    decision::*,
+    evaluation::{Evaluator, Poids},
+    state::*,
+};
+
+fn carte(ascii: &str) -> InitState {
+    StateBuilder::from_ascii(ascii).unwrap().build()
+}
+
+#[test]
+fn les_murs_font_faire_le_tour() {
+    let state = carte(
+        "ami: 0 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |# |. |A |\n\
+         |. |. |# |. |. |\n\
+         |. |. |. |. |R<|\n",
+    );
+    let distances = state.distances();
+    let root_id = *state.roots().get(Owner::Me).iter().next().unwrap();
+
+    assert_eq!(distances.owner(Owner::Me, Coord { x: 0, y: 0 }), Some(0));
+    assert_eq!(distances.owner(Owner::Me, Coord { x: 3, y: 0 }), Some(7));
+    assert_eq!(distances.owner(Owner::Me, Coord { x: 2, y: 0 }), None);
+    assert_eq!(distances.racine(root_id, Coord { x: 1, y: 2 }), Some(3));
+    assert_eq!(
+        distances.vers_proteine(Owner::Me, Coord { x: 1, y: 0 }),
+        Some(7)
+    );
+    assert_eq!(distances.proteine_la_plus_proche(Owner::Me), Some(8));
+    assert_eq!(distances.proteine_la_plus_proche(Owner::Ennemy), Some(2));
+}
+
+#[test]
+fn une_case_visee_par_un_tentacle_adverse_bloque() {
+    let state = carte(
+        "ami: 0 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |T<|R<|\n",
+    );
+    let distances = state.distances();
+
+    assert_eq!(distances.owner(Owner::Me, Coord { x: 1, y: 0 }), Some(1));
+    assert_eq!(distances.owner(Owner::Me, Coord { x: 2, y: 0 }), None);
+    assert_eq!(
+        distances.owner(Owner::Ennemy, Coord { x: 1, y: 0 }),
+        Some(2)
+    );
+}
+
+#[test]
+fn le_plateau_recalcule_apres_une_pousse() {
+    let state = carte(
+        "ami: 1 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |. |A |\n",
+    );
+    let mut plateau = Plateau::new(&state);
+    let vierge = plateau.clone();
+    assert_eq!(
+        plateau.distances().proteine_la_plus_proche(Owner::Me),
+        Some(4)
+    );
+    assert_eq!(plateau, vierge);
+
+    let root_id = plateau.a_jouer(Owner::Me)[0];
+    assert!(plateau.grow(Grow {
+        parent_id: root_id,
+        coord: Coord { x: 1, y: 0 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    }));
+    assert_eq!(
+        plateau.distances().proteine_la_plus_proche(Owner::Me),
+        Some(3)
+    );
+}
+
+#[test]
+fn l_elagage_ecarte_les_pousses_qui_s_eloignent() {
+    let state = carte(
+        "ami: 1 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |. |. |r>|. |A |\n",
+    );
+    let mut managing = Managing::new()
+        .with_nb_max_iteration(1)
+        .with_search_strategy(SearchStrategy::Faisceau)
+        .with_elagage(Elagage {
+            eloignement: true,
+            ..Elagage::default()
+        });
+    let premier_tour = planifier(&state, &mut managing, &Poids::default()).take_first_turn();
+
+    assert!(matches!(
+        premier_tour.as_slice(),
+        [Decision::Grow(grow)] if grow.coord == Coord { x: 3, y: 0 }
+    ));
+}
+
+#[test]
+fn le_poids_vers_proteine_penalise_la_distance() {
+    let state = carte(
+        "ami: 0 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |. |A |\n",
+    );
+    let poids = Poids {
+        par_case_vers_proteine: 1.5,
+        ..Poids::default()
+    };
+    assert_eq!(
+        Poids::default().evaluer(&state) - poids.evaluer(&state),
+        6.0
+    );
+}
@@ -0,0 +1,81 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    decision::*,
+    economie::Economie,
+    evaluation::{Evaluator, Poids},
+    state::*,
+};
+
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 0 1 1 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|h>|A |\n\
+         |. |. |. |\n",
+    )
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn revenu_et_projection() {
+    let economie = Economie::de(&carte(), Owner::Me);
+
+    assert_eq!(economie.revenu, Ressource::new(1, 0, 0, 0));
+    assert_eq!(economie.projeter(3), Ressource::new(3, 1, 1, 0));
+    assert_eq!(
+        Economie::de(&carte(), Owner::Ennemy).revenu,
+        Ressource::default()
+    );
+}
+
+#[test]
+fn ce_qui_est_abordable_et_quand() {
+    let economie = Economie::de(&carte(), Owner::Me);
+
+    assert_eq!(economie.nb_abordables(OrganeType::Tentacle.prix()), 1);
+    assert_eq!(economie.nb_abordables(OrganeType::Basic.prix()), 0);
+    assert_eq!(economie.tours_avant(OrganeType::Basic.prix()), Some(1));
+    assert_eq!(economie.prochain(OrganeType::Basic), Some(1));
+    assert_eq!(economie.prochain(OrganeType::Tentacle), None);
+    assert_eq!(economie.famines(), vec![Protein::D]);
+    assert_eq!(
+        economie.organes_bloques(),
+        vec![OrganeType::Harvester, OrganeType::Sporer]
+    );
+}
+
+#[test]
+fn l_elagage_refuse_d_epuiser_une_proteine_sans_revenu() {
+    let premier_tour = |famine: bool| {
+        let mut managing = Managing::new()
+            .with_nb_max_iteration(1)
+            .with_search_strategy(SearchStrategy::Faisceau)
+            .with_elagage(Elagage {
+                famine,
+                ..Elagage::default()
+            });
+        planifier(&carte(), &mut managing, &Poids::default()).take_first_turn()
+    };
+
+    assert!(matches!(
+        premier_tour(false).as_slice(),
+        [Decision::Grow(grow)] if grow.organe_type == OrganeType::Tentacle
+    ));
+    assert_eq!(premier_tour(true), vec![Decision::Wait]);
+}
+
+#[test]
+fn l_evaluation_penalise_les_famines() {
+    let state = carte();
+    let poids = Poids {
+        par_famine: 4.0,
+        ..Poids::default()
+    };
+    assert_eq!(
+        Poids::default().evaluer(&state) - poids.evaluer(&state),
+        4.0
+    );
+}
@@ -0,0 +1,80 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    ai::juger,
+    evaluation::{ErreurPoids, Evaluator, Poids},
+    state::*,
+};
+
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 1 1 1\n\
+         |. |. |. |. |. |\n\
+         |. |r>|. |B<|R<|\n",
+    )
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn poids_par_defaut_reproduisent_l_ancienne_note() {
+    // 1 + 4 protéines en stock + 1 organe ami * 3 - 2 organes ennemis * 3 + 5
+    let plateau = Plateau::new(&carte());
+    assert_eq!(Poids::default().evaluer(&plateau), 7.0);
+    assert_eq!(juger(plateau, &Poids::default()).score, 7.0);
+}
+
+#[test]
+fn un_poids_modifie_change_la_note() {
+    let poids = Poids {
+        bonus: 0.0,
+        par_organe_ami: 10.0,
+        ..Poids::default()
+    };
+    assert_eq!(poids.evaluer(&Plateau::new(&carte())), 9.0);
+}
+
+#[test]
+fn une_mauvaise_position_garde_une_note_comparable() {
+    let poids = Poids {
+        bonus: 0.0,
+        ..Poids::default()
+    };
+    let plus_d_ennemis = Poids {
+        par_organe_ennemi: 6.0,
+        ..poids
+    };
+    let plateau = Plateau::new(&carte());
+    assert_eq!(poids.evaluer(&plateau), 2.0);
+    assert_eq!(plus_d_ennemis.evaluer(&plateau), -4.0);
+}
+
+#[test]
+fn lire_relit_l_affichage() {
+    let poids = Poids::lire("# réglage\nbonus = 12.5\n\npar_recolte=-7 # commentaire\n").unwrap();
+    assert_eq!(
+        poids,
+        Poids {
+            bonus: 12.5,
+            par_recolte: -7.0,
+            ..Poids::default()
+        }
+    );
+    assert_eq!(Poids::lire(&poids.to_string()).unwrap(), poids);
+}
+
+#[test]
+fn lire_signale_les_erreurs() {
+    assert!(matches!(
+        Poids::lire("bonus = 1\nmalus = 2"),
+        Err(ErreurPoids::Cle { ligne: 2, .. })
+    ));
+    assert!(matches!(
+        Poids::lire("bonus 1"),
+        Err(ErreurPoids::Ligne { ligne: 1, .. })
+    ));
+    assert!(matches!(
+        Poids::lire("bonus = beaucoup"),
+        Err(ErreurPoids::Ligne { ligne: 1, .. })
+    ));
+}
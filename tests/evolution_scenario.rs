@@ -0,0 +1,95 @@
+use std::fmt::Debug;
+
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    decision::{Decision, Grow, Spore},
+    evaluation::Poids,
+    evolution::{muter, Genome},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use verification::{scenario::*, *};
+
+mod verification;
+
+#[test]
+fn evolution_choppe_la_proteine() -> Result<(), Box<dyn Debug>> {
+    let planif = planifier(
+        &choppe_prot(),
+        &mut managing(SearchStrategy::Evolution),
+        &Poids::default(),
+    );
+    PlanificationChecker::default()
+        .then_grow(
+            GrowChecker::default()
+                .coord(Coord { x: 1, y: 1 })
+                .organ_type(OrganeType::Basic),
+        )
+        .then_grow(
+            GrowChecker::default()
+                .coord(Coord { x: 2, y: 1 })
+                .organ_type(OrganeType::Basic),
+        )
+        .verify(planif)
+}
+
+#[test]
+fn le_meilleur_plan_est_garde_et_decale() {
+    let mut managing = managing(SearchStrategy::Evolution);
+    let planif = planifier(&choppe_prot(), &mut managing, &Poids::default());
+    assert_eq!(managing.plan_precedent(), Some(&planif));
+
+    managing.next_turn();
+    let mut attendu = planif.take_content();
+    attendu.remove(0);
+    assert_eq!(
+        managing.plan_precedent().cloned().map(|p| p.take_content()),
+        Some(attendu)
+    );
+}
+
+#[test]
+fn decaler_garde_au_moins_un_tour() {
+    let plan = Planification::from_content(vec![vec![Decision::Wait]]);
+    assert_eq!(plan.decaler(), Planification::default());
+    assert_eq!(
+        Planification::from_content(Vec::new()),
+        Planification::default()
+    );
+}
+
+#[test]
+fn le_genome_garde_est_celui_qui_a_ete_joue() {
+    let state = choppe_prot();
+    let planif = planifier(
+        &state,
+        &mut managing(SearchStrategy::Evolution),
+        &Poids::default(),
+    );
+    let nb_tours = planif.clone().take_content().len();
+    assert_eq!(rejouer_plan(&state, &planif).len(), nb_tours);
+}
+
+#[test]
+fn la_mutation_garde_la_forme_du_genome() {
+    let grow = Decision::Grow(Grow {
+        parent_id: Id::new(Owner::Me, 1),
+        coord: Coord { x: 1, y: 1 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    });
+    let spore = Decision::Spore(Spore {
+        parent_id: Id::new(Owner::Me, 2),
+        coord: Coord { x: 3, y: 1 },
+    });
+    let depart: Genome = vec![vec![grow, spore, Decision::Wait], vec![grow]];
+    let forme = |genome: &Genome| genome.iter().map(Vec::len).collect::<Vec<_>>();
+    for graine in 0..50 {
+        let mut rng = StdRng::seed_from_u64(graine);
+        let mut genome = depart.clone();
+        for _ in 0..10 {
+            muter(&mut genome, &mut rng);
+            assert_eq!(forme(&genome), forme(&depart));
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    evaluation::{Evaluator, Poids},
+    state::State,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use verification::{scenario::*, *};
+
+mod verification;
+
+fn faisceau() -> Managing {
+    managing(SearchStrategy::Faisceau)
+}
+
+#[test]
+fn le_faisceau_ne_depend_pas_du_rng() {
+    let state = choppe_prot();
+    let reference = planifier(&state, &mut faisceau(), &Poids::default());
+    for graine in 1..5 {
+        let mut managing = faisceau().with_rng(StdRng::seed_from_u64(graine));
+        assert_eq!(
+            planifier(&state, &mut managing, &Poids::default()),
+            reference
+        );
+    }
+}
+
+#[test]
+fn le_faisceau_choppe_la_proteine() {
+    let state = choppe_prot();
+    let planif = planifier(&state, &mut faisceau(), &Poids::default());
+    PlanificationChecker::default()
+        .then_grow(
+            GrowChecker::default()
+                .coord(Coord { x: 1, y: 1 })
+                .organ_type(OrganeType::Basic),
+        )
+        .then_grow(
+            GrowChecker::default()
+                .coord(Coord { x: 2, y: 1 })
+                .organ_type(OrganeType::Basic),
+        )
+        .then_grow(GrowChecker::default().organ_type(OrganeType::Basic))
+        .verify(planif)
+        .unwrap();
+}
+
+struct Neutre;
+
+impl Evaluator for Neutre {
+    fn evaluer(&self, _: &dyn State) -> f64 {
+        0.0
+    }
+}
+
+#[test]
+fn a_note_egale_le_premier_plan_du_faisceau_l_emporte() {
+    let state = choppe_prot();
+    let large = planifier(&state, &mut faisceau().with_nb_to_choose(8), &Neutre);
+    let etroit = planifier(&state, &mut faisceau().with_nb_to_choose(1), &Neutre);
+    assert_eq!(large, etroit);
+}
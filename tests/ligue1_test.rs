@@ -1,6 +1,6 @@
-use std::{collections::HashMap, rc::Rc};
+use std::collections::HashMap;
 
-use codingame::ligue1::{ai::*, atome::*, decision::*, molecule::*, state::*};
+use codingame::ligue1::{ai::*, atome::*, decision::*, evaluation::Poids, molecule::*, state::*};
 use rand::SeedableRng;
 
 #[test]
@@ -17,9 +17,9 @@ fn count_harvesting() {
             coord: Coord { x: 0, y: 0 },
             entity: Entity::Organe(Organe {
                 dir: Direction::N,
-                id: Id::new(0),
-                parent_id: Id::new(0),
-                root_id: Id::new(0),
+                id: Id::zero(Owner::Me),
+                parent_id: Id::zero(Owner::Me),
+                root_id: Id::zero(Owner::Me),
                 organe_type: OrganeType::Root,
                 owner: Owner::Me,
             }),
@@ -28,9 +28,9 @@ fn count_harvesting() {
             coord: Coord { x: 1, y: 0 },
             entity: Entity::Organe(Organe {
                 dir: Direction::E,
-                id: Id::new(1),
-                parent_id: Id::new(0),
-                root_id: Id::new(0),
+                id: Id::new(Owner::Me, 1),
+                parent_id: Id::zero(Owner::Me),
+                root_id: Id::zero(Owner::Me),
                 organe_type: OrganeType::Harvester,
                 owner: Owner::Me,
             }),
@@ -58,9 +58,11 @@ fn count_harvesting() {
             protein: Protein::A,
             direction: Direction::E,
             harvester_coord: Coord { x: 1, y: 0 },
+            harvester_id: Id::new(Owner::Me, 1),
         },
     );
-    assert_eq!(state.harvesting(), expected);
+    assert_eq!(state.harvesting().get(Owner::Me), &expected);
+    assert!(state.harvesting().get(Owner::Ennemy).is_empty());
 }
 
 #[test]
@@ -99,7 +101,7 @@ fn no_harvesting_when_no_prot() {
     let mut managing = Managing::new()
         .with_rng(rand::rngs::StdRng::seed_from_u64(62))
         .with_nb_max_iteration(1);
-    let decision = planifier(Rc::new(state), &mut managing)
+    let decision = planifier(&state, &mut managing, &Poids::default())
         .take_first_turn()
         .into_iter()
         .next()
@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+use codingame::ligue1::{ai::*, atome::*, evaluation::Poids, mcts::Rollout};
+use verification::{scenario::*, *};
+
+mod verification;
+
+#[test]
+fn mcts_choppe_la_proteine() -> Result<(), Box<dyn Debug>> {
+    for rollout in [Rollout::Aleatoire, Rollout::Heuristique] {
+        let planif = planifier(
+            &choppe_prot(),
+            &mut managing(SearchStrategy::Mcts(rollout)),
+            &Poids::default(),
+        );
+        PlanificationChecker::default()
+            .then_grow(
+                GrowChecker::default()
+                    .coord(Coord { x: 1, y: 1 })
+                    .organ_type(OrganeType::Basic),
+            )
+            .then_grow(
+                GrowChecker::default()
+                    .coord(Coord { x: 2, y: 1 })
+                    .organ_type(OrganeType::Basic),
+            )
+            .verify(planif)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn mcts_s_arrete_quand_l_arbre_est_explore() {
+    let debut = Instant::now();
+    let decisions = make_decision(
+        choppe_prot(),
+        &mut managing(SearchStrategy::Mcts(Rollout::Aleatoire)).with_nb_max_iteration(1),
+        &Poids::default(),
+    );
+
+    assert_eq!(decisions.len(), 1);
+    assert!(debut.elapsed() < Duration::from_millis(500));
+}
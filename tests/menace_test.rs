@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+
+use codingame::common::*;
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    decision::*,
+    evaluation::{Evaluator, Poids},
+    state::*,
+};
+
+fn carte(premiere_ligne: &str) -> InitState {
+    StateBuilder::from_ascii(&format!(
+        "ami: 5 5 5 5\n\
+         ennemi: 0 0 0 0\n\
+         {premiere_ligne}\n\
+         |. |. |. |. |\n"
+    ))
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn le_tentacle_menace_la_case_en_face() {
+    let state = carte("|r>|. |T<|R<|");
+    let plateau = Plateau::new(&state);
+    let attendu: HashSet<Coord> = [Coord { x: 1, y: 0 }].into();
+
+    assert!(state.est_menacee(Owner::Me, Coord { x: 1, y: 0 }));
+    assert!(!state.est_menacee(Owner::Me, Coord { x: 1, y: 1 }));
+    assert!(!state.est_menacee(Owner::Ennemy, Coord { x: 1, y: 0 }));
+    assert_eq!(state.menaces(Owner::Me), attendu);
+    assert_eq!(plateau.menaces(Owner::Me), attendu);
+    assert!(plateau.menaces(Owner::Ennemy).is_empty());
+}
+
+#[test]
+fn l_elagage_ne_pousse_pas_sous_un_tentacle() {
+    let mut managing = Managing::new()
+        .with_nb_max_iteration(2)
+        .with_search_strategy(SearchStrategy::Faisceau)
+        .with_elagage(Elagage {
+            menace: true,
+            ..Elagage::default()
+        });
+    let planification =
+        planifier(&carte("|r>|. |T<|R<|"), &mut managing, &Poids::default()).take_content();
+
+    assert!(!planification.is_empty());
+    for decision in planification.into_iter().flatten() {
+        if let Decision::Grow(grow) = decision {
+            assert_ne!(grow.coord, Coord { x: 1, y: 0 });
+        }
+    }
+}
+
+#[test]
+fn l_evaluation_penalise_les_organes_menaces() {
+    let state = carte("|r>|b>|T<|R<|");
+    let poids = Poids {
+        par_organe_menace: 7.0,
+        ..Poids::default()
+    };
+    assert_eq!(
+        Poids::default().evaluer(&state) - poids.evaluer(&state),
+        7.0
+    );
+}
@@ -0,0 +1,154 @@
+use codingame::arbitre::{entree_initiale, entree_tour, generer_carte};
+use codingame::common::*;
+use codingame::ligue1::{atome::*, decision::*, parsing::*, state::*};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn lire(entree: &str) -> Lecteur<&[u8]> {
+    Lecteur::new(entree.as_bytes())
+}
+
+#[test]
+fn chaque_type_d_entite() {
+    let entree = "0 0 WALL -1 0 X 0 0\n\
+                  1 0 A -1 0 X 0 0\n\
+                  2 0 B -1 0 X 0 0\n\
+                  3 0 C -1 0 X 0 0\n\
+                  4 0 D -1 0 X 0 0\n\
+                  0 1 ROOT 1 1 N 0 1\n\
+                  1 1 BASIC 1 2 E 1 1\n\
+                  2 1 HARVESTER 0 3 S 4 4\n\
+                  3 1 TENTACLE 0 5 W 4 4\n\
+                  4 1 SPORER 1 6 N 2 1\n";
+    let mut lecteur = lire(entree);
+    let entities: Vec<Entity> = (0..10)
+        .map(|_| parser_entity(&mut lecteur).unwrap().entity)
+        .collect();
+
+    assert_eq!(entities[0], Entity::Wall);
+    assert_eq!(entities[1..5], Protein::all().map(Entity::Protein));
+    assert_eq!(
+        entities[7],
+        Entity::Organe(Organe {
+            id: Id::new(Owner::Ennemy, 3),
+            parent_id: Id::new(Owner::Ennemy, 4),
+            root_id: Id::new(Owner::Ennemy, 4),
+            organe_type: OrganeType::Harvester,
+            dir: Direction::S,
+            owner: Owner::Ennemy,
+        })
+    );
+    let types: Vec<OrganeType> = entities[5..]
+        .iter()
+        .filter_map(|e| match e {
+            Entity::Organe(org) => Some(org.organe_type),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            OrganeType::Root,
+            OrganeType::Basic,
+            OrganeType::Harvester,
+            OrganeType::Tentacle,
+            OrganeType::Sporer
+        ]
+    );
+}
+
+#[test]
+fn erreurs_typees() {
+    let mut lecteur = lire("2 0 A -1 0 X 0 0\n0 1 ROOT -1 1 N 0 1\n");
+    assert!(parser_entity(&mut lecteur).is_ok());
+    assert_eq!(
+        parser_entity(&mut lecteur),
+        Err(ParseError {
+            ligne: 2,
+            champ: "owner",
+            brut: "0 1 ROOT -1 1 N 0 1".to_string(),
+            cause: Cause::Illisible,
+        })
+    );
+
+    let erreur = parser_entity(&mut lire("0 1 BASIC 1 1 X 0 1")).unwrap_err();
+    assert_eq!(erreur.champ, "organDir");
+    let erreur = parser_entity(&mut lire("0 1 BLOB 1 1 N 0 1")).unwrap_err();
+    assert_eq!(erreur.champ, "type");
+    let erreur = parser_resource(&mut lire("1 2 trois 4")).unwrap_err();
+    assert_eq!(erreur.champ, "proteine C");
+    let erreur = parser_dimension(&mut lire("")).unwrap_err();
+    assert_eq!(erreur.champ, "dimension");
+}
+
+#[test]
+fn les_ids_depassent_255() {
+    let cell = parser_entity(&mut lire("2 3 BASIC 1 300 E 299 256\n")).unwrap();
+    let Entity::Organe(organe) = cell.entity else {
+        panic!("organe attendu, lu {:?}", cell.entity);
+    };
+    assert_eq!(organe.id, Id::new(Owner::Me, 300));
+    assert_eq!(organe.root_id.increment(), Id::new(Owner::Me, 257));
+    assert_eq!(
+        Decision::from_command(
+            &format!("GROW {} 2 4 BASIC E", organe.id.get_num()),
+            Owner::Me
+        ),
+        Some(Decision::Grow(Grow {
+            parent_id: organe.id,
+            coord: Coord { x: 2, y: 4 },
+            organe_type: OrganeType::Basic,
+            direction: Direction::E,
+        }))
+    );
+}
+
+#[test]
+fn relit_l_entree_de_l_arbitre() {
+    let carte = generer_carte(&mut StdRng::seed_from_u64(11));
+    let entree = entree_initiale(carte.dimension()) + &entree_tour(&carte, Owner::Me);
+    let mut lecteur = lire(&entree);
+    let dimension = parser_dimension(&mut lecteur).unwrap();
+    assert_eq!(parser_tour(&mut lecteur, dimension), Ok(carte));
+
+    let state = StateBuilder::new_au_milieu().build();
+    let entree = entree_tour(&state, Owner::Me);
+    let mut lecteur = lire(&entree);
+    assert_eq!(parser_tour(&mut lecteur, state.dimension()), Ok(state));
+}
+
+#[test]
+fn un_stock_trop_grand_est_hors_bornes() {
+    let mut lecteur = lire("1 4294967296 2 3\n");
+    assert_eq!(
+        parser_resource(&mut lecteur),
+        Err(ParseError {
+            ligne: 1,
+            champ: "proteine B",
+            brut: "1 4294967296 2 3".to_string(),
+            cause: Cause::HorsBornes,
+        })
+    );
+    let mut lecteur = lire("4294967295 0 0 0\n");
+    assert_eq!(
+        parser_resource(&mut lecteur),
+        Ok(Ressource::new(u32::MAX, 0, 0, 0))
+    );
+}
+
+#[test]
+fn une_ligne_illisible_ne_desaligne_pas_le_tour_suivant() {
+    let state = StateBuilder::new_au_milieu().build();
+    let tour = entree_tour(&state, Owner::Me);
+    let entree = tour.replacen("ROOT", "BLOB", 1) + &tour;
+    let mut lecteur = lire(&entree);
+
+    let erreur = parser_tour(&mut lecteur, state.dimension()).unwrap_err();
+    assert_eq!(erreur.champ, "type");
+    assert_eq!(
+        parser_tour(&mut lecteur, state.dimension()),
+        Ok(state.clone())
+    );
+    assert!(!lecteur.est_fini());
+    assert!(parser_tour(&mut lecteur, state.dimension()).is_err());
+    assert!(lecteur.est_fini());
+}
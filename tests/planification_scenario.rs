@@ -1,8 +1,7 @@
 use std::fmt::Debug;
-use std::rc::Rc;
 
 use codingame::common::*;
-use codingame::ligue1::{ai::*, atome::*};
+use codingame::ligue1::{ai::*, atome::*, evaluation::Poids};
 use rand::SeedableRng;
 use random_testing::{random_testing, RandomTestingResult};
 use verification::*;
@@ -19,13 +18,13 @@ fn choppe_prot(rng: u64) -> Result<(), Box<dyn Debug>> {
             coord: Coord { x: 2, y: 1 },
             entity: Entity::Protein(Protein::A),
         });
-    let state = Rc::new(builder.build());
+    let state = builder.build();
     let mut managing = Managing::new()
         .with_rng(rand::rngs::StdRng::seed_from_u64(rng))
         .with_nb_max_iteration(4);
 
     // THEN
-    let planif = planifier(state, &mut managing);
+    let planif = planifier(&state, &mut managing, &Poids::default());
 
     // WHEN
     PlanificationChecker::default()
@@ -56,13 +55,13 @@ fn simple_va_harvester(rng: u64) -> Result<(), Box<dyn Debug>> {
             coord: Coord { x: 2, y: 0 },
             entity: Entity::Protein(Protein::A),
         });
-    let state = Rc::new(builder.build());
+    let state = builder.build();
     let mut managing = Managing::new()
         .with_rng(rand::rngs::StdRng::seed_from_u64(rng))
         .with_nb_max_iteration(3);
 
     // THEN
-    let planif = planifier(state, &mut managing);
+    let planif = planifier(&state, &mut managing, &Poids::default());
 
     // WHEN
     PlanificationChecker::default()
@@ -102,14 +101,14 @@ fn va_attaquer(rng: u64) -> Result<(), Box<dyn Debug>> {
         .with_ressources_ami(Ressource::new(50, 1, 1, 0))
         .add_cells(friend)
         .add_cells(ennemy);
-    let state = Rc::new(builder.build());
+    let state = builder.build();
     let mut managing = Managing::new()
         .with_rng(rand::rngs::StdRng::seed_from_u64(rng))
         .with_nb_to_choose(30)
         .with_nb_max_iteration(6);
 
     // THEN
-    let planif = planifier(state, &mut managing);
+    let planif = planifier(&state, &mut managing, &Poids::default());
 
     // WHEN
     PlanificationChecker::default()
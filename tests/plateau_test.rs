@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use codingame::common::*;
+use codingame::ligue1::{
+    atome::*,
+    decision::{Grow, Spore},
+    state::*,
+};
+
+/// Notre harvester récolte le A, un tentacle en (2, 1) atteindrait le basic ennemi.
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 1 1 1\n\
+         |A |. |. |. |. |\n\
+         |h^|r>|. |B<|R<|\n",
+    )
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn reprend_les_requetes_de_l_etat_initial() {
+    let state = carte();
+    let plateau = Plateau::new(&state);
+
+    for coord in state.dimension().coords() {
+        assert_eq!(plateau.get_by_coord(coord), state.get_by_coord(coord));
+    }
+    assert_eq!(plateau.roots(), state.roots());
+    assert_eq!(plateau.ressource(), state.ressource());
+    assert_eq!(plateau.harvesting(), state.harvesting());
+    for owner in [Owner::Me, Owner::Ennemy] {
+        assert_eq!(plateau.fertile_cell(owner), state.fertile_cell(owner));
+    }
+    for root_id in state.roots().get(Owner::Me) {
+        assert_eq!(
+            plateau.organes_by_root(*root_id),
+            state.organes_by_root(*root_id)
+        );
+        let cases =
+            |s: &dyn State| -> HashSet<Coord> { s.grow_candidate(*root_id).into_keys().collect() };
+        assert_eq!(cases(&plateau), cases(&state));
+    }
+}
+
+#[test]
+fn finir_tour_detruit_la_cible_et_recolte() {
+    let state = carte();
+    let root_id = *state.roots().get(Owner::Me).iter().next().unwrap();
+    let mut plateau = Plateau::new(&state);
+    let tentacle = Grow {
+        parent_id: root_id,
+        coord: Coord { x: 2, y: 1 },
+        organe_type: OrganeType::Tentacle,
+        direction: Direction::E,
+    };
+    assert!(plateau.grow(tentacle));
+    assert!(plateau.a_jouer(Owner::Me).is_empty());
+    assert!(!plateau.grow(tentacle));
+
+    plateau.finir_tour();
+
+    assert_eq!(
+        plateau.get_by_coord(Coord { x: 3, y: 1 }).map(|c| c.entity),
+        Some(Entity::Void)
+    );
+    assert_eq!(plateau.nb_organe(Owner::Ennemy), 1);
+    assert_eq!(
+        *plateau.ressource().get(Owner::Me),
+        Ressource::new(6, 4, 4, 5)
+    );
+    assert_eq!(plateau.a_jouer(Owner::Me), &[root_id]);
+    assert!(plateau.attacking().is_empty());
+}
+
+fn organe(state: &dyn State, x: u8, y: u8) -> Id {
+    match state.get_by_coord(Coord { x, y }).map(|c| c.entity) {
+        Some(Entity::Organe(org)) => org.id,
+        _ => panic!("pas d'organe en ({x}, {y})"),
+    }
+}
+
+#[test]
+fn grow_refuse_les_coups_illegaux_sans_rien_modifier() {
+    let state = carte();
+    let root_id = organe(&state, 1, 1);
+    let mut plateau = Plateau::new(&state);
+    let vierge = plateau.clone();
+    let pousse = |x, y, organe_type| Grow {
+        parent_id: root_id,
+        coord: Coord { x, y },
+        organe_type,
+        direction: Direction::E,
+    };
+
+    assert!(!plateau.grow(pousse(2, 0, OrganeType::Basic)));
+    assert!(!plateau.grow(pousse(2, 1, OrganeType::Root)));
+    assert_eq!(plateau, vierge);
+
+    assert!(plateau.grow(pousse(2, 1, OrganeType::Basic)));
+    let apres = plateau.clone();
+    assert!(!plateau.grow(pousse(1, 0, OrganeType::Basic)));
+    assert_eq!(plateau, apres);
+}
+
+#[test]
+fn spore_refuse_un_parent_qui_n_est_pas_un_sporer_ou_une_case_hors_de_sa_ligne() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 0 0 0 0\n\
+         |r>|s>|. |. |\n\
+         |. |. |. |R<|\n",
+    )
+    .unwrap()
+    .build();
+    let mut plateau = Plateau::new(&state);
+    let vierge = plateau.clone();
+    let spore = |parent_id, x, y| Spore {
+        parent_id,
+        coord: Coord { x, y },
+    };
+
+    assert!(!plateau.spore(spore(organe(&state, 0, 0), 3, 0)));
+    assert!(!plateau.spore(spore(organe(&state, 1, 0), 2, 1)));
+    assert_eq!(plateau, vierge);
+    assert!(plateau.spore(spore(organe(&state, 1, 0), 3, 0)));
+}
@@ -0,0 +1,35 @@
+use codingame::ligue1::{
+    ai::{Managing, Ponderation},
+    evaluation::Poids,
+};
+
+#[test]
+fn softmax_garde_les_mauvaises_notes_tirables_et_ordonnees() {
+    let poids = Ponderation::Softmax(2.0).poids(&[-30.0, -10.0, 4.0]);
+    assert_eq!(poids[2], 1.0);
+    assert!(poids.iter().all(|p| *p > 0.0));
+    assert!(poids[0] < poids[1] && poids[1] < poids[2]);
+}
+
+#[test]
+fn la_temperature_resserre_le_tirage() {
+    let chaud = Ponderation::Softmax(100.0).poids(&[0.0, 10.0]);
+    let froid = Ponderation::Softmax(1.0).poids(&[0.0, 10.0]);
+    assert!(froid[0] < chaud[0]);
+}
+
+#[test]
+fn rang_ne_depend_que_de_l_ordre() {
+    assert_eq!(
+        Ponderation::Rang.poids(&[-1e9, 7.0, -3.0]),
+        vec![1.0, 3.0, 2.0]
+    );
+}
+
+#[test]
+fn par_defaut_une_recolte_manquee_pese_moins_d_un_cinquieme() {
+    let ponderation = Managing::new().ponderation();
+    assert_eq!(ponderation, Ponderation::Softmax(2.0));
+    let poids = ponderation.poids(&[-Poids::default().par_recolte, 0.0]);
+    assert!(poids[0] < 0.2 * poids[1]);
+}
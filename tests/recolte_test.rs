@@ -0,0 +1,150 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    atome::*,
+    decision::*,
+    evaluation::{Evaluator, Poids},
+    molecule::OwnerMap,
+    state::*,
+};
+
+fn carte(ascii: &str) -> InitState {
+    StateBuilder::from_ascii(ascii).unwrap().build()
+}
+
+/// Deux de nos harvesters et un harvester ennemi visent la même protéine A.
+fn partage() -> InitState {
+    carte(
+        "ami: 0 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|h>|A |H<|R<|\n\
+         |. |b>|h^|. |. |\n",
+    )
+}
+
+/// L'ennemi mange la protéine que notre harvester vise.
+fn mangee() -> InitState {
+    carte(
+        "ami: 0 0 0 0\n\
+         ennemi: 1 0 0 0\n\
+         |r>|h>|A |B<|R<|\n",
+    )
+}
+
+fn racine(state: &dyn State, owner: Owner) -> Id {
+    *state.roots().get(owner).iter().next().unwrap()
+}
+
+#[test]
+fn une_recolte_par_case_et_par_joueur() {
+    let state = partage();
+    let recoltes = state.harvesting();
+    let cible = Coord { x: 2, y: 0 };
+
+    assert_eq!(recoltes.get(Owner::Me).len(), 1);
+    assert_eq!(
+        recoltes.get(Owner::Me)[&cible].harvester_coord,
+        Coord { x: 1, y: 0 }
+    );
+    assert_eq!(
+        recoltes.get(Owner::Ennemy)[&cible].harvester_coord,
+        Coord { x: 3, y: 0 }
+    );
+    assert_eq!(Plateau::new(&state).harvesting(), recoltes);
+}
+
+#[test]
+fn le_revenu_du_plateau_est_celui_de_l_arbitre() {
+    let state = partage();
+    let mut plateau = Plateau::new(&state);
+    plateau.finir_tour();
+    let arbitre = simuler_tour(&state, OwnerMap::new(Vec::new(), Vec::new()));
+
+    // Nos deux harvesters visent la même case A: l'arbitre n'en donne qu'une unité, comme au
+    // harvester ennemi.
+    let attendu = OwnerMap::new(Ressource::new(1, 0, 0, 0), Ressource::new(1, 0, 0, 0));
+    assert_eq!(plateau.ressource(), attendu);
+    assert_eq!(arbitre.ressource(), attendu);
+}
+
+#[test]
+fn une_proteine_mangee_ne_se_recolte_plus() {
+    let state = mangee();
+    let grow = Grow {
+        parent_id: state
+            .organes_by_root(racine(&state, Owner::Ennemy))
+            .into_iter()
+            .find(|org| org.organe_type == OrganeType::Basic)
+            .unwrap()
+            .id,
+        coord: Coord { x: 2, y: 0 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::W,
+    };
+    let mut plateau = Plateau::new(&state);
+    assert!(plateau.grow(grow));
+    plateau.wait(racine(&state, Owner::Me));
+    plateau.finir_tour();
+    let arbitre = simuler_tour(
+        &state,
+        OwnerMap::new(vec![Decision::Wait], vec![Decision::Grow(grow)]),
+    );
+
+    assert!(plateau.harvesting().get(Owner::Me).is_empty());
+    assert_eq!(plateau.ressource().get(Owner::Me), &Ressource::default());
+    assert_eq!(plateau.ressource(), arbitre.ressource());
+}
+
+#[test]
+fn le_revenu_perdu_d_une_case_recoltee() {
+    let state = mangee();
+
+    assert_eq!(
+        state.revenu_perdu(Coord { x: 2, y: 0 }),
+        OwnerMap::new(Ressource::new(1, 0, 0, 0), Ressource::default())
+    );
+    assert_eq!(
+        state.revenu_perdu(Coord { x: 3, y: 0 }),
+        OwnerMap::default()
+    );
+}
+
+#[test]
+fn manger_sa_propre_recolte_est_note() {
+    let state = carte(
+        "ami: 1 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|h>|A |\n",
+    );
+    let harvester = state
+        .organes_by_root(racine(&state, Owner::Me))
+        .into_iter()
+        .find(|org| org.organe_type == OrganeType::Harvester)
+        .unwrap();
+    let mut plateau = Plateau::new(&state);
+    assert!(plateau.grow(Grow {
+        parent_id: harvester.id,
+        coord: Coord { x: 2, y: 0 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    }));
+    plateau.finir_tour();
+    plateau.finir_tour();
+
+    assert!(plateau.harvesting().get(Owner::Me).is_empty());
+    assert_eq!(
+        plateau.ressource().get(Owner::Me),
+        &Ressource::new(3, 0, 0, 0)
+    );
+    assert_eq!(
+        plateau.revenu_sacrifie().get(Owner::Me),
+        &Ressource::new(1, 0, 0, 0)
+    );
+    let poids = Poids {
+        par_revenu_sacrifie: 5.0,
+        ..Poids::default()
+    };
+    assert_eq!(
+        Poids::default().evaluer(&plateau) - poids.evaluer(&plateau),
+        5.0
+    );
+}
@@ -0,0 +1,67 @@
+use codingame::common::*;
+use codingame::ligue1::{atome::*, decision::Grow, rendu::Rendu, state::*};
+
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 2 3 4\n\
+         |. |A |. |. |\n\
+         |r>|. |B<|R<|\n",
+    )
+    .unwrap()
+    .build()
+}
+
+#[test]
+fn affiche_un_etat_intermediaire() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 2 3 4\n\
+         |r>|. |A |. |\n\
+         |r>|. |B<|R<|\n",
+    )
+    .unwrap()
+    .build();
+    let racine = |y| match state.get_by_coord(Coord { x: 0, y }) {
+        Some(Cell {
+            entity: Entity::Organe(org),
+            ..
+        }) => org.id,
+        _ => panic!("pas de racine en (0, {y})"),
+    };
+    let mut plateau = Plateau::new(&state);
+    assert!(plateau.grow(Grow {
+        parent_id: racine(1),
+        coord: Coord { x: 1, y: 1 },
+        organe_type: OrganeType::Tentacle,
+        direction: Direction::E,
+    }));
+    assert!(plateau.grow(Grow {
+        parent_id: racine(0),
+        coord: Coord { x: 1, y: 0 },
+        organe_type: OrganeType::Harvester,
+        direction: Direction::E,
+    }));
+
+    assert_eq!(
+        Rendu(&plateau).to_string(),
+        "ami: 5 4 3 4\n\
+         ennemi: 1 2 3 4\n\
+         |r> |h> |A *|.  |\n\
+         |r> |t> |B<!|R< |\n"
+    );
+
+    let relu = StateBuilder::from_ascii(&Rendu(&plateau).to_string())
+        .unwrap()
+        .build();
+    assert_eq!(dessiner(&relu), dessiner(&plateau));
+}
+
+#[test]
+fn display_de_l_etat_initial() {
+    let state = carte();
+    assert_eq!(state.to_string(), Rendu(&state).to_string());
+    assert!(state
+        .to_string()
+        .starts_with("ami: 5 5 5 5\nennemi: 1 2 3 4\n"));
+}
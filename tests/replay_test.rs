@@ -0,0 +1,76 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::PathBuf,
+};
+
+use codingame::arbitre::{entree_initiale, entree_tour, generer_carte};
+use codingame::ligue1::{
+    ai::*, atome::*, decision::Decision, evaluation::Poids, molecule::OwnerMap, parsing::*,
+    replay::*, state::*,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn enregistre_puis_relit_une_partie() {
+    let chemin = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("replay.txt");
+    let carte = generer_carte(&mut StdRng::seed_from_u64(21));
+    let suivant = simuler_tour(
+        &carte,
+        OwnerMap::new(vec![Decision::Wait], vec![Decision::Wait]),
+    );
+    let entree = entree_initiale(carte.dimension())
+        + &entree_tour(&carte, Owner::Me)
+        + &entree_tour(&suivant, Owner::Me);
+
+    let nouveau_managing = || Managing::new().with_nb_max_iteration(1);
+    let mut managing = nouveau_managing();
+    let mut joues: Vec<(InitState, Vec<Decision>)> = Vec::new();
+    {
+        let fichier = BufWriter::new(File::create(&chemin).unwrap());
+        let mut lecteur =
+            Lecteur::new(entree.as_bytes()).with_enregistreur(Enregistreur::new(Box::new(fichier)));
+        let dimension = parser_dimension(&mut lecteur).unwrap();
+        for _ in 0..2 {
+            managing.restart();
+            lecteur.enregistreur().unwrap().nouveau_tour();
+            let state = parser_tour(&mut lecteur, dimension).unwrap();
+            let decisions = make_decision(state.clone(), &mut managing, &Poids::default());
+            managing.next_turn();
+            for decision in decisions.iter() {
+                lecteur
+                    .enregistreur()
+                    .unwrap()
+                    .sortie(&decision.to_command());
+            }
+            joues.push((state, decisions));
+        }
+        lecteur.enregistreur().unwrap().nouveau_tour();
+    }
+
+    let replay = charger_replay(BufReader::new(File::open(&chemin).unwrap())).unwrap();
+    assert_eq!(replay.dimension, carte.dimension());
+    let relus: Vec<(InitState, Vec<Decision>)> = replay
+        .tours
+        .into_iter()
+        .map(|tour| (tour.state, tour.decisions))
+        .collect();
+    assert_eq!(relus, joues);
+
+    // Un managing neuf, mené comme `main`, rejoue exactement les mêmes décisions.
+    let mut managing = nouveau_managing();
+    for (tour, (state, decisions)) in relus.into_iter().enumerate() {
+        managing.restart();
+        let rejoue = make_decision(state, &mut managing, &Poids::default());
+        managing.next_turn();
+        assert_eq!(rejoue, decisions, "tour {tour}");
+    }
+}
+
+#[test]
+fn erreur_avec_la_ligne_du_fichier() {
+    let replay = "> 3 1\n# tour 0\n> 1\n> 0 0 ROOT 1 0 N 0 0\n> 1 1 1 1\n> 1 1 x 1\n";
+    let erreur = charger_replay(replay.as_bytes()).unwrap_err();
+    assert_eq!(erreur.ligne, 6);
+    assert_eq!(erreur.champ, "proteine C");
+}
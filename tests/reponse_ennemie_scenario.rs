@@ -0,0 +1,127 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    decision::{Decision, Grow},
+    evaluation::Poids,
+    molecule::OwnerMap,
+    state::*,
+};
+use rand::SeedableRng;
+
+/// En poussant au milieu, on se met à portée d'un tentacle ennemi en (2, 1).
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 1 1 1\n\
+         |. |. |. |. |\n\
+         |r>|. |. |R<|\n\
+         |. |. |. |. |\n",
+    )
+    .unwrap()
+    .build()
+}
+
+/// Le plateau avant le tour et celui où l'on a poussé en `coord`, tour non terminé.
+fn pousser(state: &InitState, coord: Coord) -> (Plateau, Plateau) {
+    let root_id = *state.roots().get(Owner::Me).iter().next().unwrap();
+    let avant = Plateau::new(state);
+    let mut joue = avant.clone();
+    assert!(joue.grow(Grow {
+        parent_id: root_id,
+        coord,
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    }));
+    (avant, joue)
+}
+
+#[test]
+fn le_pire_cas_trouve_le_tentacle() {
+    let (avant, joue) = pousser(&carte(), Coord { x: 1, y: 1 });
+    let mut managing = Managing::new().with_reponse_ennemie(ReponseEnnemie::PireCas(3));
+    let pire = repondre(&avant, joue.clone(), &mut managing, &Poids::default());
+    let ignore = repondre(&avant, joue, &mut Managing::new(), &Poids::default());
+
+    assert_eq!(
+        pire.state
+            .get_by_coord(Coord { x: 1, y: 1 })
+            .map(|c| c.entity),
+        Some(Entity::Void)
+    );
+    assert!(matches!(
+        pire.state.get_by_coord(Coord { x: 2, y: 1 }).map(|c| c.entity),
+        Some(Entity::Organe(org)) if org.organe_type == OrganeType::Tentacle && org.dir == Direction::W
+    ));
+    assert!(pire.score < ignore.score);
+    assert_eq!(pire.state.planification(), ignore.state.planification());
+}
+
+#[test]
+fn la_reponse_est_simultanee_et_la_collision_donne_un_mur() {
+    let state = StateBuilder::from_ascii(
+        "ami: 1 0 0 0\n\
+         ennemi: 1 0 0 0\n\
+         |r>|. |R<|\n",
+    )
+    .unwrap()
+    .build();
+    let milieu = Coord { x: 1, y: 0 };
+    let (avant, joue) = pousser(&state, milieu);
+    let mut managing = Managing::new().with_reponse_ennemie(ReponseEnnemie::PireCas(3));
+    let pire = repondre(&avant, joue, &mut managing, &Poids::default());
+
+    assert_eq!(
+        pire.state.get_by_coord(milieu).map(|c| c.entity),
+        Some(Entity::Wall)
+    );
+    assert_eq!(pire.state.ressource(), OwnerMap::default());
+    assert_eq!(pire.state.nb_organe(Owner::Me), 1);
+}
+
+#[test]
+fn la_planification_ne_contient_que_nos_decisions() {
+    for reponse in [ReponseEnnemie::PireCas(2), ReponseEnnemie::Echantillon(2)] {
+        let mut managing = Managing::new()
+            .with_rng(rand::rngs::StdRng::seed_from_u64(4))
+            .with_nb_max_iteration(2)
+            .with_reponse_ennemie(reponse);
+        let planification = planifier(&carte(), &mut managing, &Poids::default()).take_content();
+
+        assert!(planification.iter().all(|tour| tour.len() <= 1));
+        for decision in planification.into_iter().flatten() {
+            let owner = match decision {
+                Decision::Grow(grow) => grow.parent_id.get_owner(),
+                Decision::Spore(spore) => spore.parent_id.get_owner(),
+                Decision::Wait => Owner::Me,
+            };
+            assert_eq!(owner, Owner::Me);
+        }
+    }
+}
+
+#[test]
+fn l_echantillon_rend_la_reponse_la_plus_proche_de_la_moyenne() {
+    let (avant, joue) = pousser(&carte(), Coord { x: 1, y: 1 });
+    let managing = |n| {
+        Managing::new()
+            .with_rng(rand::rngs::StdRng::seed_from_u64(7))
+            .with_reponse_ennemie(ReponseEnnemie::Echantillon(n))
+    };
+    // Tirées une à une avec la même graine, ce sont les réponses de l'échantillon.
+    let mut une_a_une = managing(1);
+    let reponses: Vec<WeightedState> = (0..6)
+        .map(|_| repondre(&avant, joue.clone(), &mut une_a_une, &Poids::default()))
+        .collect();
+    assert!(reponses.iter().any(|w| w.score != reponses[0].score));
+    let moyenne = reponses.iter().map(|w| w.score).sum::<f64>() / 6.0;
+
+    let echantillon = repondre(&avant, joue, &mut managing(6), &Poids::default());
+    assert_eq!(echantillon.score, moyenne);
+    let ecart = |w: &WeightedState| (w.score - moyenne).abs();
+    let rendue = reponses
+        .iter()
+        .find(|w| w.state == echantillon.state)
+        .unwrap();
+    assert!(reponses.iter().all(|w| ecart(rendue) <= ecart(w)));
+}
@@ -0,0 +1,210 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    ai::*,
+    atome::*,
+    decision::{Decision, Grow},
+    evaluation::Poids,
+    molecule::OwnerMap,
+    state::{simuler_tour, InitState, Plateau, State},
+};
+use rand::SeedableRng;
+
+fn carte(milieu: &str) -> InitState {
+    StateBuilder::from_ascii(&format!(
+        "ami: 2 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |. |. |. |\n\
+         |r>|. |{milieu}|\n\
+         |. |. |. |\n"
+    ))
+    .unwrap()
+    .build()
+}
+
+fn plan(state: &InitState) -> Planification {
+    let parent_id = *state.roots().get(Owner::Me).iter().next().unwrap();
+    let basic = |x: u8, parent_id: Id| {
+        Decision::Grow(Grow {
+            parent_id,
+            coord: Coord { x, y: 1 },
+            organe_type: OrganeType::Basic,
+            direction: Direction::E,
+        })
+    };
+    Planification::from_content(vec![
+        vec![basic(1, parent_id)],
+        vec![basic(2, parent_id.increment())],
+        vec![],
+    ])
+}
+
+#[test]
+fn rejoue_les_tours_encore_valides() {
+    let state = carte("A ");
+    let plateaux = rejouer_plan(&state, &plan(&state));
+
+    assert_eq!(plateaux.len(), 2);
+    assert_eq!(
+        plateaux[1].planification().take_content()[..2],
+        plan(&state).take_content()[..2]
+    );
+    assert_eq!(
+        *plateaux[1].ressource().get(Owner::Me),
+        Ressource::new(3, 0, 0, 0)
+    );
+}
+
+#[test]
+fn s_arrete_sur_une_case_prise_ou_un_parent_disparu() {
+    let bloque = carte("# ");
+    assert_eq!(rejouer_plan(&bloque, &plan(&carte("A "))).len(), 1);
+
+    let sans_ressource = StateBuilder::from_ascii("ami: 0 0 0 0\nennemi: 0 0 0 0\n|r>|. |. |\n")
+        .unwrap()
+        .build();
+    assert!(rejouer_plan(&sans_ressource, &plan(&carte("A "))).is_empty());
+
+    let autre_racine = carte("A ");
+    let parent_disparu = Planification::from_content(vec![vec![Decision::Grow(Grow {
+        parent_id: Id::new(Owner::Me, 42),
+        coord: Coord { x: 1, y: 1 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    })]]);
+    assert!(rejouer_plan(&autre_racine, &parent_disparu).is_empty());
+}
+
+#[test]
+fn le_plan_est_garde_pour_le_tour_suivant() {
+    let state = carte("A ");
+    let mut managing = Managing::new()
+        .with_rng(rand::rngs::StdRng::seed_from_u64(2))
+        .with_nb_max_iteration(3)
+        .with_search_strategy(SearchStrategy::Faisceau);
+    let planification = planifier(&state, &mut managing, &Poids::default());
+    assert_eq!(managing.plan_precedent(), Some(&planification));
+
+    // Une fois le premier tour joué, la suite du plan reste valide sur le nouvel état.
+    let premier_tour = planification.take_first_turn();
+    let suivant = simuler_tour(&state, OwnerMap::new(premier_tour, Vec::new()));
+    managing.next_turn();
+    let plan_suivant = managing.plan_precedent().cloned().unwrap();
+    assert_eq!(
+        rejouer_plan(&suivant, &plan_suivant).len(),
+        plan_suivant.take_content().len() - 1
+    );
+}
+
+#[test]
+fn le_plan_repris_subit_aussi_le_pire_cas() {
+    // En (1, 1), la pousse est à portée d'un tentacle ennemi poussé en (2, 1).
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 1 1 1\n\
+         |. |. |. |. |\n\
+         |r>|. |. |R<|\n\
+         |. |. |. |. |\n",
+    )
+    .unwrap()
+    .build();
+    let root_id = *state.roots().get(Owner::Me).iter().next().unwrap();
+    let dans_le_tentacle = Decision::Grow(Grow {
+        parent_id: root_id,
+        coord: Coord { x: 1, y: 1 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    });
+    let mut managing = Managing::new()
+        .with_nb_max_iteration(1)
+        .with_search_strategy(SearchStrategy::Faisceau)
+        .with_reponse_ennemie(ReponseEnnemie::PireCas(3));
+    managing.garder_plan(Planification::from_content(vec![vec![dans_le_tentacle]]));
+
+    let premier_tour = planifier(&state, &mut managing, &Poids::default()).take_first_turn();
+    assert_eq!(premier_tour.len(), 1);
+    assert_ne!(premier_tour[0], dans_le_tentacle);
+}
+
+#[test]
+fn les_coups_sont_rattaches_a_leur_racine_par_id() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |r>|. |\n",
+    )
+    .unwrap()
+    .build();
+    let mut racines: Vec<Id> = state.roots().get(Owner::Me).iter().copied().collect();
+    racines.sort_by_key(|id| id.get_num());
+    let pousse = Decision::Grow(Grow {
+        parent_id: racines[1],
+        coord: Coord { x: 4, y: 0 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    });
+
+    let plateaux = rejouer_plan(
+        &state,
+        &Planification::from_content(vec![vec![pousse, Decision::Wait]]),
+    );
+    assert_eq!(plateaux.len(), 1);
+    assert_eq!(
+        plateaux[0].planification().take_content()[0],
+        vec![Decision::Wait, pousse]
+    );
+}
+
+#[test]
+fn un_id_decale_par_l_ennemi_fait_tomber_la_suite() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 5 5 5 5\n\
+         |. |. |. |. |. |\n\
+         |r>|. |. |. |R<|\n\
+         |. |. |. |. |. |\n",
+    )
+    .unwrap()
+    .build();
+    let racine = |owner| *state.roots().get(owner).iter().next().unwrap();
+    let basic = |parent_id: Id, x: u8| {
+        Decision::Grow(Grow {
+            parent_id,
+            coord: Coord { x, y: 1 },
+            organe_type: OrganeType::Basic,
+            direction: Direction::E,
+        })
+    };
+    // Le plan a été fait en supposant que l'ennemi attend.
+    let mut prevu = Plateau::new(&state);
+    prevu.resoudre(OwnerMap::new(vec![Decision::Wait], Vec::new()));
+    prevu.resoudre(OwnerMap::new(vec![basic(racine(Owner::Me), 1)], Vec::new()));
+    let id_prevu = match prevu.get_by_coord(Coord { x: 1, y: 1 }).map(|c| c.entity) {
+        Some(Entity::Organe(org)) => org.id,
+        _ => panic!("la pousse prévue manque"),
+    };
+    prevu.resoudre(OwnerMap::new(vec![basic(id_prevu, 2)], Vec::new()));
+    let suite = Planification::from_content(prevu.planification().take_content()[1..].to_vec());
+
+    let ennemi_attend = simuler_tour(&state, OwnerMap::new(vec![Decision::Wait], Vec::new()));
+    assert_eq!(rejouer_plan(&ennemi_attend, &suite).len(), 2);
+
+    // L'ennemi prend l'id que le plan croyait nôtre: le tour qui s'en sert est abandonné.
+    let ennemi_pousse = simuler_tour(
+        &state,
+        OwnerMap::new(
+            vec![Decision::Wait],
+            vec![Decision::Grow(Grow {
+                parent_id: racine(Owner::Ennemy),
+                coord: Coord { x: 4, y: 0 },
+                organe_type: OrganeType::Basic,
+                direction: Direction::N,
+            })],
+        ),
+    );
+    let rejoue = rejouer_plan(&ennemi_pousse, &suite);
+    assert_eq!(rejoue.len(), 1);
+    assert_eq!(
+        rejoue[0].planification().take_content()[..1],
+        suite.take_content()[..1]
+    );
+}
@@ -0,0 +1,55 @@
+use codingame::ligue1::{atome::*, parsing::*};
+
+const GROS: u32 = u32::MAX - 1;
+
+#[test]
+fn les_operateurs_saturent() {
+    let plein = Ressource::new(GROS, 0, 3, 1);
+    assert_eq!(
+        plein + Ressource::new(5, 1, 0, 0),
+        Ressource::new(u32::MAX, 1, 3, 1)
+    );
+    assert_eq!(
+        plein - Ressource::new(0, 2, 1, 1),
+        Ressource::new(GROS, 0, 2, 0)
+    );
+    assert_eq!(plein * 2, Ressource::new(u32::MAX, 0, 6, 2));
+    assert_eq!(plein.total(), u32::MAX);
+}
+
+#[test]
+fn checked_signale_le_depassement() {
+    let plein = Ressource::new(GROS, 0, 3, 1);
+    assert_eq!(plein.checked_add(Ressource::new(2, 0, 0, 0)), None);
+    assert_eq!(plein.checked_sub(Ressource::new(0, 1, 0, 0)), None);
+    assert_eq!(plein.checked_mul(2), None);
+    assert_eq!(
+        plein.checked_sub(Ressource::new(1, 0, 3, 0)),
+        Some(Ressource::new(GROS - 1, 0, 0, 1))
+    );
+}
+
+#[test]
+fn l_ordre_dit_si_l_on_peut_payer() {
+    let stock = Ressource::new(2, 1, 1, 1);
+    assert!(stock >= OrganeType::Basic.prix());
+    assert!(stock >= Ressource::new(1, 1, 1, 1));
+    assert!(stock < Ressource::new(2, 1, 1, 2));
+    // Assez de A mais pas de B: ni plus grand ni plus petit.
+    let prix = Ressource::new(1, 2, 0, 0);
+    assert_eq!(stock.partial_cmp(&prix), None);
+}
+
+#[test]
+fn le_parsing_accepte_les_grands_stocks() {
+    let mut lecteur = Lecteur::new("300 0 70000 4\n".as_bytes());
+    assert_eq!(
+        parser_resource(&mut lecteur).unwrap(),
+        Ressource::new(300, 0, 70000, 4)
+    );
+
+    let mut lecteur = Lecteur::new("beaucoup 0 0 0\n".as_bytes());
+    let erreur = parser_resource(&mut lecteur).unwrap_err();
+    assert_eq!(erreur.champ, "proteine A");
+    assert_eq!(erreur.ligne, 1);
+}
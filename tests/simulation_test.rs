@@ -0,0 +1,247 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    ai,
+    atome::*,
+    decision::*,
+    evaluation::{Evaluator, Poids},
+    molecule::*,
+    state::*,
+};
+
+fn grow(owner: Owner, parent: u16, coord: Coord, organe_type: OrganeType) -> Decision {
+    Decision::Grow(Grow {
+        parent_id: Id::new(owner, parent),
+        coord,
+        organe_type,
+        direction: Direction::E,
+    })
+}
+
+#[test]
+fn les_deux_joueurs_poussent_en_meme_temps() {
+    let ennemy = OrganismBuilder::default().build(Owner::Ennemy, Coord { x: 0, y: 0 });
+    let state = StateBuilder::new_au_milieu().add_cells(ennemy).build();
+
+    let suivant = simuler_tour(
+        &state,
+        OwnerMap::new(
+            vec![grow(Owner::Me, 0, Coord { x: 2, y: 1 }, OrganeType::Basic)],
+            vec![grow(
+                Owner::Ennemy,
+                0,
+                Coord { x: 1, y: 0 },
+                OrganeType::Basic,
+            )],
+        ),
+    );
+
+    assert_eq!(suivant.nb_organe(Owner::Me), 2);
+    assert_eq!(suivant.nb_organe(Owner::Ennemy), 2);
+    assert_eq!(
+        suivant.ressource().get(Owner::Me),
+        &Ressource::new(49, 1, 1, 1)
+    );
+    assert_eq!(
+        suivant.ressource().get(Owner::Ennemy),
+        &Ressource::new(49, 50, 50, 50)
+    );
+}
+
+#[test]
+fn collision_donne_un_mur() {
+    let ennemy = OrganismBuilder::default().build(Owner::Ennemy, Coord { x: 2, y: 0 });
+    let state = StateBuilder::new_ligne_de_3_root_a_gauche()
+        .add_cells(ennemy)
+        .build();
+    let milieu = Coord { x: 1, y: 0 };
+
+    let suivant = simuler_tour(
+        &state,
+        OwnerMap::new(
+            vec![grow(Owner::Me, 0, milieu, OrganeType::Basic)],
+            vec![grow(Owner::Ennemy, 0, milieu, OrganeType::Basic)],
+        ),
+    );
+
+    assert_eq!(
+        suivant.get_by_coord(milieu),
+        Some(Cell {
+            coord: milieu,
+            entity: Entity::Wall
+        })
+    );
+    assert_eq!(
+        suivant.ressource().get(Owner::Me),
+        &Ressource::new(0, 1, 1, 1)
+    );
+    assert_eq!(
+        suivant.ressource().get(Owner::Ennemy),
+        &Ressource::new(0, 1, 1, 1)
+    );
+}
+
+#[test]
+fn tentacule_detruit_les_descendants() {
+    let friend = OrganismBuilder::default().build(Owner::Me, Coord { x: 0, y: 1 });
+    let ennemy = OrganismBuilder::default()
+        .add_basic(Direction::E)
+        .add_basic(Direction::E)
+        .add_basic(Direction::N)
+        .build(Owner::Ennemy, Coord { x: 0, y: 2 });
+    let state = StateBuilder::new_carre_vide_3()
+        .add_cells(friend)
+        .add_cells(ennemy)
+        .build();
+
+    let suivant = simuler_tour(
+        &state,
+        OwnerMap::new(
+            vec![Decision::Grow(Grow {
+                parent_id: Id::zero(Owner::Me),
+                coord: Coord { x: 1, y: 1 },
+                organe_type: OrganeType::Tentacle,
+                direction: Direction::S,
+            })],
+            vec![Decision::Wait],
+        ),
+    );
+
+    assert_eq!(suivant.nb_organe(Owner::Ennemy), 1);
+    assert_eq!(suivant.nb_organe(Owner::Me), 2);
+    assert_eq!(
+        suivant.get_by_coord(Coord { x: 2, y: 1 }),
+        Some(Cell {
+            coord: Coord { x: 2, y: 1 },
+            entity: Entity::Void
+        })
+    );
+}
+
+#[test]
+fn recolte_et_consommation_de_proteine() {
+    let state = StateBuilder::new_a_gauche_prot_a_a_droite()
+        .with_ressources_ami(Ressource::new(0, 0, 1, 1))
+        .add_cell(Cell {
+            coord: Coord { x: 0, y: 1 },
+            entity: Entity::Protein(Protein::B),
+        })
+        .build();
+
+    let suivant = simuler_tour(
+        &state,
+        OwnerMap::new(
+            vec![Decision::Grow(Grow {
+                parent_id: Id::zero(Owner::Me),
+                coord: Coord { x: 0, y: 1 },
+                organe_type: OrganeType::Harvester,
+                direction: Direction::N,
+            })],
+            vec![],
+        ),
+    );
+    assert_eq!(
+        suivant.ressource().get(Owner::Me),
+        &Ressource::new(0, 3, 0, 0)
+    );
+
+    let harvester = StateBuilder::new_ligne_de_3_root_a_gauche()
+        .with_ressources_ami(Ressource::new(0, 0, 1, 1))
+        .add_cell(Cell {
+            coord: Coord { x: 2, y: 0 },
+            entity: Entity::Protein(Protein::A),
+        })
+        .build();
+    let suivant = simuler_tour(
+        &harvester,
+        OwnerMap::new(
+            vec![Decision::Grow(Grow {
+                parent_id: Id::zero(Owner::Me),
+                coord: Coord { x: 1, y: 0 },
+                organe_type: OrganeType::Harvester,
+                direction: Direction::E,
+            })],
+            vec![],
+        ),
+    );
+    assert_eq!(
+        suivant.ressource().get(Owner::Me),
+        &Ressource::new(1, 0, 0, 0)
+    );
+    assert_eq!(suivant.harvesting().get(Owner::Me).len(), 1);
+}
+
+#[test]
+fn resoudre_garde_la_planification_et_penalise_les_wait() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |. |R<|\n",
+    )
+    .unwrap()
+    .build();
+    let root_id = *state.roots().get(Owner::Me).iter().next().unwrap();
+    let mut attente = Plateau::new(&state);
+    let mut active = attente.clone();
+    let parent = |plateau: &Plateau, x| match plateau.get_by_coord(Coord { x, y: 0 }) {
+        Some(Cell {
+            entity: Entity::Organe(org),
+            ..
+        }) => org.id,
+        _ => root_id,
+    };
+    for x in [1, 2] {
+        attente.resoudre(OwnerMap::new(vec![Decision::Wait], Vec::new()));
+        let pousse = Decision::Grow(Grow {
+            parent_id: parent(&active, x - 1),
+            coord: Coord { x, y: 0 },
+            organe_type: OrganeType::Basic,
+            direction: Direction::E,
+        });
+        active.resoudre(OwnerMap::new(vec![pousse], Vec::new()));
+    }
+
+    assert_eq!(
+        attente.planification().take_content(),
+        vec![vec![Decision::Wait], vec![Decision::Wait], vec![]]
+    );
+    assert_eq!(active.nb_organe(Owner::Me), 3);
+    // Sans valeur pour les organes, seuls les WAIT départagent.
+    let poids = Poids {
+        par_organe_ami: 0.0,
+        ..Poids::default()
+    };
+    assert!(poids.evaluer(&attente) < poids.evaluer(&active));
+}
+
+#[test]
+fn resoudre_range_les_decisions_dans_l_ordre_des_racines() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |r>|. |\n",
+    )
+    .unwrap()
+    .build();
+    let mut racines: Vec<Id> = state.roots().get(Owner::Me).iter().copied().collect();
+    racines.sort_by_key(|id| id.get_num());
+    let seconde = state.get_by_id(racines[1]).unwrap();
+    let pousse = Decision::Grow(Grow {
+        parent_id: racines[1],
+        coord: Coord {
+            x: seconde.coord.x + 1,
+            y: 0,
+        },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    });
+
+    let mut plateau = Plateau::new(&state);
+    plateau.resoudre(OwnerMap::new(vec![Decision::Wait, pousse], Vec::new()));
+
+    let planification = plateau.planification();
+    assert_eq!(
+        planification.clone().take_content()[0],
+        vec![Decision::Wait, pousse]
+    );
+    assert_eq!(ai::rejouer_plan(&state, &planification).len(), 1);
+}
@@ -0,0 +1,63 @@
+use std::fmt::Debug;
+
+use codingame::common::StateBuilder;
+use codingame::ligue1::{
+    ai::*, atome::*, decision::Decision, evaluation::Poids, smitsimax::Decouplage, state::State,
+};
+use verification::{scenario::*, *};
+
+mod verification;
+
+#[test]
+fn smitsimax_choppe_la_proteine() -> Result<(), Box<dyn Debug>> {
+    for decouplage in [Decouplage::ParJoueur, Decouplage::ParRacine] {
+        let planif = planifier(
+            &choppe_prot(),
+            &mut managing(SearchStrategy::Smitsimax(decouplage)),
+            &Poids::default(),
+        );
+        PlanificationChecker::default()
+            .then_grow(
+                GrowChecker::default()
+                    .coord(Coord { x: 1, y: 1 })
+                    .organ_type(OrganeType::Basic),
+            )
+            .then_grow(
+                GrowChecker::default()
+                    .coord(Coord { x: 2, y: 1 })
+                    .organ_type(OrganeType::Basic),
+            )
+            .verify(planif)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn une_decision_par_racine_amie() {
+    let state = StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 5 5 5 5\n\
+         |r>|. |. |. |R<|\n\
+         |. |. |. |. |. |\n\
+         |r>|. |. |. |R<|\n",
+    )
+    .unwrap()
+    .build();
+    let planification = planifier(
+        &state,
+        &mut managing(SearchStrategy::Smitsimax(Decouplage::ParRacine)),
+        &Poids::default(),
+    );
+
+    let tours = planification.take_content();
+    assert_eq!(tours[0].len(), 2);
+    for decision in tours.into_iter().flatten() {
+        let owner = match decision {
+            Decision::Grow(grow) => grow.parent_id.get_owner(),
+            Decision::Spore(spore) => spore.parent_id.get_owner(),
+            Decision::Wait => Owner::Me,
+        };
+        assert_eq!(owner, Owner::Me);
+    }
+    assert_eq!(state.roots().get(Owner::Me).len(), 2);
+}
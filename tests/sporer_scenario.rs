@@ -1,8 +1,8 @@
-use std::{fmt::Debug, rc::Rc};
+use std::fmt::Debug;
 mod verification;
 use codingame::{
     common::*,
-    ligue1::{ai::*, atome::*},
+    ligue1::{ai::*, atome::*, evaluation::Poids},
 };
 use rand::SeedableRng;
 use verification::{
@@ -14,7 +14,7 @@ fn creer_sporer_quand_seule_ressource_dispo(rng: u64) -> Result<(), Box<dyn Debu
     let builder = StateBuilder::new_ligne_de_3_root_a_gauche()
         .with_ressources_ami(Ressource::new(0, 1, 0, 1));
 
-    let state = Rc::new(builder.build());
+    let state = builder.build();
 
     let mut managing = Managing::new()
         .with_rng(rand::rngs::StdRng::seed_from_u64(rng))
@@ -22,7 +22,7 @@ fn creer_sporer_quand_seule_ressource_dispo(rng: u64) -> Result<(), Box<dyn Debu
         .with_nb_max_iteration(2);
 
     // THEN
-    let planification = planifier(state, &mut managing);
+    let planification = planifier(&state, &mut managing, &Poids::default());
 
     PlanificationChecker::default()
         .then_grow(
@@ -0,0 +1,88 @@
+use codingame::common::*;
+use codingame::ligue1::{
+    atome::*,
+    decision::*,
+    evaluation::{Evaluator, Poids},
+    state::*,
+};
+
+fn carte(ascii: &str) -> InitState {
+    StateBuilder::from_ascii(ascii).unwrap().build()
+}
+
+fn couloir() -> InitState {
+    carte(
+        "ami: 1 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|. |. |. |. |. |R<|\n",
+    )
+}
+
+fn avec_mur() -> InitState {
+    carte(
+        "ami: 0 0 0 0\n\
+         ennemi: 0 0 0 0\n\
+         |r>|A |. |B |. |R<|\n\
+         |. |# |. |. |. |. |\n",
+    )
+}
+
+#[test]
+fn le_milieu_atteint_ensemble_reste_neutre() {
+    let territoire = couloir().territoire();
+
+    assert_eq!(territoire.nb_cases(Owner::Me), 3);
+    assert_eq!(territoire.nb_cases(Owner::Ennemy), 3);
+    assert_eq!(territoire.controleur(Coord { x: 2, y: 0 }), Some(Owner::Me));
+    assert_eq!(territoire.controleur(Coord { x: 3, y: 0 }), None);
+    assert_eq!(
+        territoire.controleur(Coord { x: 4, y: 0 }),
+        Some(Owner::Ennemy)
+    );
+}
+
+#[test]
+fn le_mur_rallonge_le_chemin_et_les_proteines_sont_comptees_par_type() {
+    let territoire = avec_mur().territoire();
+
+    assert_eq!(territoire.nb_cases(Owner::Me), 5);
+    assert_eq!(territoire.nb_cases(Owner::Ennemy), 6);
+    assert_eq!(
+        territoire.controleur(Coord { x: 3, y: 1 }),
+        Some(Owner::Ennemy)
+    );
+    assert_eq!(territoire.nb_proteines(Owner::Me, Protein::A), 1);
+    assert_eq!(territoire.nb_proteines(Owner::Me, Protein::B), 0);
+    assert_eq!(territoire.nb_proteines(Owner::Ennemy, Protein::B), 1);
+}
+
+#[test]
+fn une_pousse_gagne_du_territoire() {
+    let mut plateau = Plateau::new(&couloir());
+    assert_eq!(plateau.territoire().nb_cases(Owner::Me), 3);
+
+    let root_id = plateau.a_jouer(Owner::Me)[0];
+    assert!(plateau.grow(Grow {
+        parent_id: root_id,
+        coord: Coord { x: 1, y: 0 },
+        organe_type: OrganeType::Basic,
+        direction: Direction::E,
+    }));
+    assert_eq!(plateau.territoire().nb_cases(Owner::Me), 4);
+    assert_eq!(plateau.territoire().nb_cases(Owner::Ennemy), 3);
+}
+
+#[test]
+fn l_evaluation_compte_l_avance_territoriale() {
+    let state = avec_mur();
+    let poids = Poids {
+        par_case_controlee: 2.0,
+        par_proteine_controlee: 10.0,
+        ..Poids::default()
+    };
+    // Une case de retard; A et B se compensent.
+    assert_eq!(
+        poids.evaluer(&state) - Poids::default().evaluer(&state),
+        -2.0
+    );
+}
@@ -1,14 +1,17 @@
 use std::fmt::Debug;
 pub mod random_testing;
+pub mod scenario;
 use codingame::ligue1::{
     atome::{Coord, Direction, OrganeType, Planification},
     decision::{Decision, Grow},
 };
 use itertools::Itertools;
 
+type DecisionAssertion = Box<dyn Fn(Option<Decision>) -> Option<DecisionFailType>>;
+
 #[derive(Default)]
 pub struct PlanificationChecker {
-    assertions: Vec<Box<dyn Fn(Option<Decision>) -> Option<DecisionFailType>>>,
+    assertions: Vec<DecisionAssertion>,
 }
 
 impl PlanificationChecker {
@@ -37,14 +40,15 @@ impl PlanificationChecker {
         }
     }
 
+    #[allow(dead_code)]
     pub fn then_finis(mut self) -> Self {
-        self.assertions.push(Box::new(|decision| match decision {
-            None => None,
-            Some(_sinon) => Some(DecisionFailType::PasFinis),
+        self.assertions.push(Box::new(|decision| {
+            decision.map(|_sinon| DecisionFailType::PasFinis)
         }));
         self
     }
 
+    #[allow(dead_code)]
     pub fn then_wait(mut self) -> Self {
         self.assertions.push(Box::new(|decision| match decision {
             Some(d) => match d {
@@ -80,6 +84,7 @@ impl GrowChecker {
         self.assertions.iter().filter_map(|f| f(grow)).collect()
     }
 
+    #[allow(dead_code)]
     pub fn direction(mut self, direction: Direction) -> Self {
         self.assertions.push(Box::new(move |grow| {
             if grow.direction == direction {
@@ -131,6 +136,7 @@ pub enum DecisionFailType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum GrowFail {
     BadDirection { actual: Direction },
     BadOrganType { actual: OrganeType },
@@ -4,6 +4,7 @@ use itertools::Itertools;
 
 pub type RandomTestingResult = Result<(), RandomTestingFail>;
 
+#[allow(dead_code)]
 pub fn random_testing<F, E>(function: F, sample_size: u64, nb_max_err: usize) -> RandomTestingResult
 where
     F: Fn(u64) -> Result<(), E>,
@@ -59,7 +60,9 @@ impl<E: Debug> PartialEq for ErrorWrapper<E> {
 
 impl<E: Debug> Eq for ErrorWrapper<E> {}
 
+// Les champs ne sont lus que par `Debug`, dans le message d'échec du test.
 #[derive(Debug)]
+#[allow(dead_code)]
 pub struct RandomTestingFail {
     expected: usize,
     actual: usize,
@@ -0,0 +1,26 @@
+use codingame::common::StateBuilder;
+use codingame::ligue1::{
+    ai::{Managing, SearchStrategy},
+    state::InitState,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+#[allow(dead_code)]
+pub fn choppe_prot() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 2 0 0 0\n\
+         |. |. |. |\n\
+         |r |. |A |\n\
+         |. |. |. |\n",
+    )
+    .unwrap()
+    .build()
+}
+
+#[allow(dead_code)]
+pub fn managing(search_strategy: SearchStrategy) -> Managing {
+    Managing::new()
+        .with_rng(StdRng::seed_from_u64(3))
+        .with_nb_max_iteration(3)
+        .with_search_strategy(search_strategy)
+}
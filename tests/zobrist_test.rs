@@ -0,0 +1,70 @@
+use codingame::common::*;
+use codingame::ligue1::{atome::*, decision::Grow, state::*};
+
+fn carte() -> InitState {
+    StateBuilder::from_ascii(
+        "ami: 5 5 5 5\n\
+         ennemi: 1 1 1 1\n\
+         |. |. |. |. |. |\n\
+         |. |r>|. |B<|R<|\n",
+    )
+    .unwrap()
+    .build()
+}
+
+fn basic(state: &dyn State, x: u8, y: u8, direction: Direction) -> Grow {
+    Grow {
+        parent_id: *state.roots().get(Owner::Me).iter().next().unwrap(),
+        coord: Coord { x, y },
+        organe_type: OrganeType::Basic,
+        direction,
+    }
+}
+
+fn pousser(state: &InitState, coups: &[(u8, u8)]) -> Plateau {
+    let mut plateau = Plateau::new(state);
+    for (x, y) in coups {
+        assert!(plateau.grow(basic(&plateau, *x, *y, Direction::N)));
+        plateau.finir_tour();
+    }
+    plateau
+}
+
+fn relire(state: &dyn State) -> InitState {
+    StateBuilder::from_ascii(&dessiner(state)).unwrap().build()
+}
+
+#[test]
+fn deux_ordres_de_pousse_meme_empreinte() {
+    let state = carte();
+    let premier = pousser(&state, &[(0, 1), (1, 0)]);
+    let second = pousser(&state, &[(1, 0), (0, 1)]);
+
+    assert_ne!(premier.planification(), second.planification());
+    assert_eq!(premier.zobrist(), second.zobrist());
+    assert_ne!(premier.zobrist(), state.zobrist());
+    assert_eq!(premier.zobrist(), relire(&premier).zobrist());
+}
+
+#[test]
+fn l_empreinte_suit_les_directions_et_les_destructions() {
+    let state = carte();
+    assert_eq!(Plateau::new(&state).zobrist(), state.zobrist());
+
+    let mut nord = Plateau::new(&state);
+    let mut sud = Plateau::new(&state);
+    assert!(nord.grow(basic(&state, 0, 1, Direction::N)));
+    assert!(sud.grow(basic(&state, 0, 1, Direction::S)));
+    assert_ne!(nord.zobrist(), sud.zobrist());
+
+    let mut attaque = Plateau::new(&state);
+    let tentacle = Grow {
+        organe_type: OrganeType::Tentacle,
+        ..basic(&state, 2, 1, Direction::E)
+    };
+    assert!(attaque.grow(tentacle));
+    let avant = attaque.zobrist();
+    attaque.finir_tour();
+    assert_ne!(attaque.zobrist(), avant);
+    assert_eq!(attaque.zobrist(), relire(&attaque).zobrist());
+}